@@ -36,11 +36,38 @@ fn benchmark_formatting(criterion: &mut Criterion) {
 	});
 }
 
+fn benchmark_compact_encodings(criterion: &mut Criterion) {
+	let uuid = Uuid128::empty(); // 00000000-0000-7000-8000-000000000000
+	let base32 = uuid.to_base32();
+	let base58 = uuid.to_base58();
+	let base64url = uuid.to_base64url();
+
+	criterion.bench_function("format_base32", |bencher| {
+		bencher.iter(|| black_box(&uuid).to_base32())
+	});
+	criterion.bench_function("parse_base32", |bencher| {
+		bencher.iter(|| Uuid128::from_base32(black_box(&base32)).unwrap())
+	});
+	criterion.bench_function("format_base58", |bencher| {
+		bencher.iter(|| black_box(&uuid).to_base58())
+	});
+	criterion.bench_function("parse_base58", |bencher| {
+		bencher.iter(|| Uuid128::from_base58(black_box(&base58)).unwrap())
+	});
+	criterion.bench_function("format_base64url", |bencher| {
+		bencher.iter(|| black_box(&uuid).to_base64url())
+	});
+	criterion.bench_function("parse_base64url", |bencher| {
+		bencher.iter(|| Uuid128::from_base64url(black_box(&base64url)).unwrap())
+	});
+}
+
 criterion_group!(
 	benches,
 	benchmark_encoding,
 	benchmark_decoding,
 	benchmark_parsing,
 	benchmark_formatting,
+	benchmark_compact_encodings,
 );
 criterion_main!(benches);
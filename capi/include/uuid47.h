@@ -0,0 +1,160 @@
+#ifndef UUID47_H
+#define UUID47_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#define UUID47_CAPI 1
+
+#if defined(UUID47_CAPI)
+// Length of a formatted UUID string, excluding the terminating NUL.
+#define UUID47_STRING_LEN 36
+#endif
+
+#if defined(UUID47_CAPI)
+// Status code returned by every C API function.
+typedef enum Uuid47Status {
+#if defined(UUID47_CAPI)
+  // Success.
+  UUID47_STATUS_OK = 0,
+#endif
+#if defined(UUID47_CAPI)
+  // A required pointer argument was NULL.
+  UUID47_STATUS_NULL_POINTER = 1,
+#endif
+#if defined(UUID47_CAPI)
+  // The output buffer is too small.
+  UUID47_STATUS_BUFFER_TOO_SMALL = 2,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`UuidParseError::InvalidLength`].
+  UUID47_STATUS_PARSE_INVALID_LENGTH = 10,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`UuidParseError::InvalidHex`].
+  UUID47_STATUS_PARSE_INVALID_HEX = 11,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`UuidValidationError::InvalidLength`].
+  UUID47_STATUS_INVALID_LENGTH = 20,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`UuidValidationError::InvalidVersion`].
+  UUID47_STATUS_INVALID_VERSION = 21,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`UuidValidationError::InvalidVariant`].
+  UUID47_STATUS_INVALID_VARIANT = 22,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`UuidValidationError::Sentinel`].
+  UUID47_STATUS_SENTINEL = 23,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`WeakKeyError::Zero`].
+  UUID47_STATUS_WEAK_KEY_ZERO = 30,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`WeakKeyError::ExampleKey`].
+  UUID47_STATUS_WEAK_KEY_EXAMPLE = 31,
+#endif
+#if defined(UUID47_CAPI)
+  // See [`WeakKeyError::LowEntropy`].
+  UUID47_STATUS_WEAK_KEY_LOW_ENTROPY = 32,
+#endif
+} Uuid47Status;
+#endif
+
+#if defined(UUID47_CAPI)
+// Key for `UUIDv47` encoding/decoding.
+typedef struct Uuid47Key {
+  // First 64 bits of the key.
+  uint64_t k0;
+  // Second 64 bits of the key.
+  uint64_t k1;
+} Uuid47Key;
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+#if defined(UUID47_CAPI)
+// Creates a key from two 64-bit halves.
+//
+// Weak keys (see `UuidV47Key::validate`) are refused unless `allow_weak_key` is true.
+//
+// # Safety
+//
+// `out` must point to a writable `Uuid47Key`.
+enum Uuid47Status uuid47_key_new(uint64_t k0,
+                                 uint64_t k1,
+                                 bool allow_weak_key,
+                                 struct Uuid47Key *out);
+#endif
+
+#if defined(UUID47_CAPI)
+// Creates a key from 16 bytes (`k0` then `k1`, each little-endian).
+//
+// Weak keys (see `UuidV47Key::validate`) are refused unless `allow_weak_key` is true.
+//
+// # Safety
+//
+// `bytes` must point to 16 readable bytes and `out` to a writable `Uuid47Key`.
+enum Uuid47Status uuid47_key_from_bytes(const uint8_t *bytes,
+                                        bool allow_weak_key,
+                                        struct Uuid47Key *out);
+#endif
+
+#if defined(UUID47_CAPI)
+// Encodes a `UUIDv7` into `UUIDv4` facade.
+//
+// The input is validated, the Nil and Max UUID are passed through unchanged.
+//
+// # Safety
+//
+// `v7` and `out` must point to 16 bytes, `key` to a valid `Uuid47Key`.
+enum Uuid47Status uuid47_encode(const uint8_t *v7, const struct Uuid47Key *key, uint8_t *out);
+#endif
+
+#if defined(UUID47_CAPI)
+// Decodes a `UUIDv4` facade back into `UUIDv7`.
+//
+// The input is validated, the Nil and Max UUID are passed through unchanged.
+//
+// # Safety
+//
+// `facade` and `out` must point to 16 bytes, `key` to a valid `Uuid47Key`.
+enum Uuid47Status uuid47_decode(const uint8_t *facade, const struct Uuid47Key *key, uint8_t *out);
+#endif
+
+#if defined(UUID47_CAPI)
+// Parses a UUID string of `len` bytes (not necessarily NUL-terminated) into 16 bytes.
+//
+// # Safety
+//
+// `s` must point to `len` readable bytes and `out` to 16 writable bytes.
+enum Uuid47Status uuid47_parse(const char *s, size_t len, uint8_t *out);
+#endif
+
+#if defined(UUID47_CAPI)
+// Formats 16 bytes as a NUL-terminated UUID string.
+//
+// `buf_len` must be at least `UUID47_STRING_LEN + 1`.
+//
+// # Safety
+//
+// `uuid` must point to 16 readable bytes and `buf` to `buf_len` writable bytes.
+enum Uuid47Status uuid47_format(const uint8_t *uuid, char *buf, size_t buf_len);
+#endif
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* UUID47_H */
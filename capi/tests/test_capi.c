@@ -0,0 +1,75 @@
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "uuid47.h"
+
+static void test_roundtrip(void) {
+	const char *s = "00000000-0000-7000-8000-000000000000";
+	Uuid47Key key;
+	uint8_t v7[16], facade[16], back[16];
+	char buf[UUID47_STRING_LEN + 1];
+
+	/* the example key is refused unless explicitly allowed */
+	assert(uuid47_key_new(0x0123456789abcdefULL, 0xfedcba9876543210ULL, false, &key) == UUID47_STATUS_WEAK_KEY_EXAMPLE);
+	assert(uuid47_key_new(0x0123456789abcdefULL, 0xfedcba9876543210ULL, true, &key) == UUID47_STATUS_OK);
+
+	assert(uuid47_parse(s, strlen(s), v7) == UUID47_STATUS_OK);
+	assert(uuid47_encode(v7, &key, facade) == UUID47_STATUS_OK);
+	assert(uuid47_format(facade, buf, sizeof(buf)) == UUID47_STATUS_OK);
+	assert(strcmp(buf, "22d97126-9609-4000-8000-000000000000") == 0);
+
+	assert(uuid47_decode(facade, &key, back) == UUID47_STATUS_OK);
+	assert(memcmp(v7, back, 16) == 0);
+}
+
+static void test_key_from_bytes(void) {
+	const uint8_t bytes[16] = {
+		0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01,
+		0x10, 0x32, 0x54, 0x76, 0x98, 0xba, 0xdc, 0xfe,
+	};
+	Uuid47Key key;
+
+	assert(uuid47_key_from_bytes(bytes, false, &key) == UUID47_STATUS_WEAK_KEY_EXAMPLE);
+	assert(uuid47_key_from_bytes(bytes, true, &key) == UUID47_STATUS_OK);
+	assert(key.k0 == 0x0123456789abcdefULL);
+	assert(key.k1 == 0xfedcba9876543210ULL);
+
+	const uint8_t zero[16] = {0};
+	assert(uuid47_key_from_bytes(zero, false, &key) == UUID47_STATUS_WEAK_KEY_ZERO);
+	assert(uuid47_key_new(0x5f1c9a0e7d3b2846ULL, 0xb4e8126fa0c37d95ULL, false, &key) == UUID47_STATUS_OK);
+}
+
+static void test_errors(void) {
+	Uuid47Key key;
+	uint8_t out[16];
+	char small[UUID47_STRING_LEN];
+
+	assert(uuid47_key_new(1, 2, true, &key) == UUID47_STATUS_OK);
+	assert(uuid47_key_new(1, 2, false, NULL) == UUID47_STATUS_NULL_POINTER);
+
+	const char *bad_hex = "zzzzzzzz-0000-7000-8000-000000000000";
+	assert(uuid47_parse(bad_hex, strlen(bad_hex), out) == UUID47_STATUS_PARSE_INVALID_HEX);
+	assert(uuid47_parse("0000", 4, out) == UUID47_STATUS_PARSE_INVALID_LENGTH);
+	assert(uuid47_parse(NULL, 0, out) == UUID47_STATUS_NULL_POINTER);
+
+	const uint8_t v1[16] = {0, 0, 0, 0, 0, 0, 0x10, 0, 0x80};
+	assert(uuid47_encode(v1, &key, out) == UUID47_STATUS_INVALID_VERSION);
+
+	const uint8_t bad_variant[16] = {0, 0, 0, 0, 0, 0, 0x70, 0, 0x00};
+	assert(uuid47_encode(bad_variant, &key, out) == UUID47_STATUS_INVALID_VARIANT);
+
+	const uint8_t nil[16] = {0};
+	assert(uuid47_format(nil, small, sizeof(small)) == UUID47_STATUS_BUFFER_TOO_SMALL);
+	assert(uuid47_encode(nil, &key, out) == UUID47_STATUS_OK);
+	assert(memcmp(nil, out, 16) == 0);
+}
+
+int main(void) {
+	test_roundtrip();
+	test_key_from_bytes();
+	test_errors();
+
+	puts("capi: all tests passed");
+	return 0;
+}
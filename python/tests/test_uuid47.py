@@ -0,0 +1,97 @@
+"""Tests for the `uuid47` Python bindings.
+
+Build and install the extension module first:
+
+    maturin develop --release
+    python -m unittest discover python/tests
+"""
+
+import unittest
+import uuid
+
+import uuid47
+
+KEY = uuid47.UuidV47Key(0x0123456789ABCDEF, 0xFEDCBA9876543210, allow_weak_key=True)
+V7 = "00000000-0000-7000-8000-000000000000"
+FACADE = "22d97126-9609-4000-8000-000000000000"
+
+
+class Uuid128Test(unittest.TestCase):
+    def test_parse_format(self):
+        u = uuid47.parse(V7)
+        self.assertEqual(str(u), V7)
+        self.assertEqual(u.version, 7)
+        self.assertEqual(repr(u), f"Uuid128('{V7}')")
+        self.assertEqual(uuid47.Uuid128(V7), u)
+
+    def test_parse_errors(self):
+        with self.assertRaises(ValueError):
+            uuid47.parse("not-a-uuid")
+        with self.assertRaises(ValueError):
+            uuid47.Uuid128(b"\x00" * 15)
+
+    def test_uuid_interop(self):
+        py_uuid = uuid.UUID(V7)
+        u = uuid47.Uuid128(py_uuid)
+        self.assertEqual(u.to_uuid(), py_uuid)
+        self.assertEqual(u.bytes, py_uuid.bytes)
+        self.assertEqual(uuid47.Uuid128(py_uuid.bytes), u)
+
+    def test_ordering_and_hash(self):
+        a = uuid47.Uuid128("00000000-0001-7000-8000-000000000000")
+        b = uuid47.Uuid128("00000000-0002-7000-8000-000000000000")
+        self.assertLess(a, b)
+        self.assertEqual(len({a, b, uuid47.Uuid128(str(a))}), 2)
+
+
+class FacadeTest(unittest.TestCase):
+    def test_encode_decode(self):
+        facade = uuid47.encode_as_v4facade(V7, KEY)
+        self.assertEqual(str(facade), FACADE)
+        self.assertEqual(facade.version, 4)
+        self.assertEqual(str(uuid47.decode_from_v4facade(facade, KEY)), V7)
+        self.assertEqual(str(uuid47.parse(V7).encode_as_v4facade(KEY)), FACADE)
+
+    def test_wrong_version(self):
+        with self.assertRaises(ValueError):
+            uuid47.decode_from_v4facade(V7, KEY)
+
+    def test_weak_key(self):
+        with self.assertRaises(ValueError):
+            uuid47.UuidV47Key(0x0123456789ABCDEF, 0xFEDCBA9876543210)
+        with self.assertRaises(ValueError):
+            uuid47.UuidV47Key(0, 0)
+        uuid47.UuidV47Key(0x5F1C9A0E7D3B2846, 0xB4E8126FA0C37D95)
+
+    def test_key_repr_hides_secret(self):
+        self.assertNotIn("0123", repr(KEY))
+        self.assertEqual(KEY.k0, 0x0123456789ABCDEF)
+
+    def test_many(self):
+        facades = uuid47.encode_many([V7, uuid.UUID(V7)], KEY)
+        self.assertEqual(facades, [uuid.UUID(FACADE)] * 2)
+        self.assertEqual(uuid47.decode_many(facades, KEY), [uuid.UUID(V7)] * 2)
+
+    def test_bytes(self):
+        data = uuid.UUID(V7).bytes * 3
+        encoded = uuid47.encode_bytes(data, KEY)
+        self.assertEqual(encoded, uuid.UUID(FACADE).bytes * 3)
+        self.assertEqual(uuid47.decode_bytes(encoded, KEY), data)
+        with self.assertRaises(ValueError):
+            uuid47.encode_bytes(data[:-1], KEY)
+
+    def test_numpy(self):
+        try:
+            import numpy as np
+        except ImportError:
+            self.skipTest("numpy is not installed")
+
+        array = np.frombuffer(uuid.UUID(V7).bytes * 4, dtype="S16")
+        encoded = np.frombuffer(uuid47.encode_bytes(array.tobytes(), KEY), dtype="S16")
+        self.assertEqual(encoded[0], uuid.UUID(FACADE).bytes)
+        decoded = np.frombuffer(uuid47.decode_bytes(encoded.tobytes(), KEY), dtype="S16")
+        self.assertTrue((decoded == array).all())
+
+
+if __name__ == "__main__":
+    unittest.main()
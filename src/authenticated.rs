@@ -0,0 +1,235 @@
+use crate::error::AuthenticatedFacadeError;
+use crate::key::UuidV47Key;
+use crate::siphash::siphash24;
+use crate::uuid::Uuid128;
+
+/// Smallest supported tag size, in bits.
+pub const MIN_TAG_BITS: u8 = 8;
+
+/// Largest supported tag size, in bits.
+pub const MAX_TAG_BITS: u8 = 48;
+
+/// `UUIDv4` facades carrying a keyed MAC tag, to reject forged or tampered IDs before any lookup.
+///
+/// The tag takes the `tag_bits` least significant bits of `rand_b`, computed with SipHash-2-4
+/// over the rest of the facade. Those bits must be zero in the `UUIDv7`
+/// (generate IDs with [`AuthenticatedFacade::clear_tag_bits`]), which leaves `74 - tag_bits` random bits.
+///
+/// A forged facade is accepted with probability `2^-tag_bits` per attempt:
+///
+/// | Tag bits | Random bits left | Forgery probability |
+/// | --- | --- | --- |
+/// | 8 | 66 | 1 in 256 |
+/// | 16 | 58 | 1 in 65 536 |
+/// | 24 | 50 | 1 in 16.7 million |
+/// | 32 | 42 | 1 in 4.3 billion |
+/// | 48 | 26 | 1 in 2.8 × 10^14 |
+///
+/// **Warning:** below 16 bits the tag only filters out typos and random garbage,
+/// an attacker gets a forged facade accepted after a few hundred attempts. Rate-limit lookups if you use it.
+///
+/// Fewer random bits means more collisions for IDs created in the same millisecond,
+/// and an easier-to-guess `UUIDv7` if the key leaks.
+///
+/// Both the timestamp mask and the tag use the reference SipHash-2-4 with the `UuidV47Key`,
+/// the [`FacadePrf`](crate::FacadePrf) backends cannot be used with this mode.
+///
+/// # Examples
+/// ```
+/// use uuid47::{AuthenticatedFacade, AuthenticatedFacadeError, Uuid128, UuidV47Key};
+///
+/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+/// let auth = AuthenticatedFacade::new(key, 24).unwrap();
+///
+/// let v7 = auth.clear_tag_bits(&"01912345-6789-7abc-9def-0123456789ab".parse().unwrap());
+/// let facade = auth.encode(&v7).unwrap();
+/// assert_eq!(auth.decode(&facade), Ok(v7));
+///
+/// let tampered = Uuid128::from_u128(facade.to_u128() ^ (1 << 80)).unwrap();
+/// assert_eq!(auth.decode(&tampered), Err(AuthenticatedFacadeError::InvalidTag));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AuthenticatedFacade {
+	key: UuidV47Key,
+	tag_bits: u8,
+}
+
+impl AuthenticatedFacade {
+	/// Creates an authenticated mode with a `tag_bits` tag.
+	///
+	/// # Errors
+	///
+	/// * [`AuthenticatedFacadeError::InvalidTagBits`] - if `tag_bits` is not between [`MIN_TAG_BITS`] and [`MAX_TAG_BITS`].
+	pub fn new(key: UuidV47Key, tag_bits: u8) -> Result<Self, AuthenticatedFacadeError> {
+		if !(MIN_TAG_BITS..=MAX_TAG_BITS).contains(&tag_bits) {
+			return Err(AuthenticatedFacadeError::InvalidTagBits);
+		}
+
+		Ok(Self { key, tag_bits })
+	}
+
+	/// Get the tag size, in bits.
+	#[must_use]
+	pub fn tag_bits(&self) -> u8 {
+		self.tag_bits
+	}
+
+	fn tag_mask(&self) -> u128 {
+		(1 << self.tag_bits) - 1
+	}
+
+	/// Zero the bits of `v7` where the tag goes, making it encodable.
+	#[must_use]
+	pub fn clear_tag_bits(&self, v7: &Uuid128) -> Uuid128 {
+		let mut out = *v7;
+		out.set_rand_b(v7.rand_b() & !(self.tag_mask() as u64));
+		out
+	}
+
+	/// Tag of a facade whose tag bits are zero.
+	fn tag(&self, untagged: &Uuid128) -> u128 {
+		// domain separation from the facade, Feistel and migration messages
+		let mut msg = [0u8; 21];
+		msg[0..4].copy_from_slice(b"u47t");
+		msg[4..20].copy_from_slice(untagged.as_bytes());
+		msg[20] = self.tag_bits;
+
+		u128::from(siphash24(&msg, self.key.k0, self.key.k1)) & self.tag_mask()
+	}
+
+	/// Encode a `UUIDv7` into an authenticated `UUIDv4` facade.
+	///
+	/// # Errors
+	///
+	/// * [`AuthenticatedFacadeError::Sentinel`] - if this is the Nil or Max UUID.
+	/// * [`AuthenticatedFacadeError::InvalidVersion`] - if this is not a `UUIDv7`.
+	/// * [`AuthenticatedFacadeError::TagBitsNotZero`] - if the tag bits of the `UUIDv7` are not zero.
+	pub fn encode(&self, v7: &Uuid128) -> Result<Uuid128, AuthenticatedFacadeError> {
+		if v7.is_nil() || v7.is_max() {
+			return Err(AuthenticatedFacadeError::Sentinel);
+		}
+		if v7.uuid_version() != 7 {
+			return Err(AuthenticatedFacadeError::InvalidVersion);
+		}
+		if v7.to_u128() & self.tag_mask() != 0 {
+			return Err(AuthenticatedFacadeError::TagBitsNotZero);
+		}
+
+		let untagged = v7.encode_as_v4facade(&self.key);
+		let mut out = untagged;
+		out.set_rand_b(untagged.rand_b() | self.tag(&untagged) as u64);
+
+		Ok(out)
+	}
+
+	/// Verify the tag of an authenticated `UUIDv4` facade and decode it back into `UUIDv7`.
+	///
+	/// # Errors
+	///
+	/// * [`AuthenticatedFacadeError::Sentinel`] - if this is the Nil or Max UUID.
+	/// * [`AuthenticatedFacadeError::InvalidVersion`] - if this is not a `UUIDv4`.
+	/// * [`AuthenticatedFacadeError::InvalidTag`] - if the tag does not verify.
+	pub fn decode(&self, facade: &Uuid128) -> Result<Uuid128, AuthenticatedFacadeError> {
+		if facade.is_nil() || facade.is_max() {
+			return Err(AuthenticatedFacadeError::Sentinel);
+		}
+		if facade.uuid_version() != 4 {
+			return Err(AuthenticatedFacadeError::InvalidVersion);
+		}
+
+		let untagged = self.clear_tag_bits(facade);
+		let tag = facade.to_u128() & self.tag_mask();
+
+		if tag != self.tag(&untagged) {
+			return Err(AuthenticatedFacadeError::InvalidTag);
+		}
+
+		Ok(untagged.decode_from_v4facade(&self.key))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::builder::UuidV7Builder;
+
+	#[test]
+	fn test_authenticated_roundtrip() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+
+		for tag_bits in [MIN_TAG_BITS, 16, 32, MAX_TAG_BITS] {
+			let auth = AuthenticatedFacade::new(key, tag_bits).unwrap();
+
+			for i in 0..64u64 {
+				let v7 = auth.clear_tag_bits(
+					&UuidV7Builder::new()
+						.timestamp_ms(i.wrapping_mul(0x9e37_79b9_7f4a) >> 16)
+						.rand_a(i.wrapping_mul(31) as u16)
+						.rand_b(i.wrapping_mul(0x2545_f491_4f6c_dd1d))
+						.build(),
+				);
+
+				let facade = auth.encode(&v7).unwrap();
+				assert_eq!(facade.uuid_version(), 4);
+				assert_eq!(auth.decode(&facade), Ok(v7));
+
+				// every payload bit flip is detected, smaller tags let 2^-tag_bits of them through
+				if tag_bits < 32 {
+					continue;
+				}
+				let payload_bits = (0..62).chain(64..76).chain(80..128);
+				for bit in payload_bits {
+					let flipped = Uuid128::from_u128(facade.to_u128() ^ (1 << bit)).unwrap();
+					assert_eq!(
+						auth.decode(&flipped),
+						Err(AuthenticatedFacadeError::InvalidTag)
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn test_authenticated_errors() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		let auth = AuthenticatedFacade::new(key, 16).unwrap();
+
+		assert_eq!(
+			AuthenticatedFacade::new(key, 7),
+			Err(AuthenticatedFacadeError::InvalidTagBits)
+		);
+		assert_eq!(
+			AuthenticatedFacade::new(key, 49),
+			Err(AuthenticatedFacadeError::InvalidTagBits)
+		);
+
+		let v7 = UuidV7Builder::new().rand_b(1).build();
+		assert_eq!(
+			auth.encode(&v7),
+			Err(AuthenticatedFacadeError::TagBitsNotZero)
+		);
+		assert_eq!(
+			auth.encode(&Uuid128::nil()),
+			Err(AuthenticatedFacadeError::Sentinel)
+		);
+		assert_eq!(
+			auth.decode(&v7),
+			Err(AuthenticatedFacadeError::InvalidVersion)
+		);
+
+		// a plain facade does not verify (with overwhelming probability)
+		let plain = Uuid128::empty().encode_as_v4facade(&key);
+		assert_eq!(
+			auth.decode(&plain),
+			Err(AuthenticatedFacadeError::InvalidTag)
+		);
+
+		// a different key does not verify
+		let other = AuthenticatedFacade::new(UuidV47Key::new(1, 2), 16).unwrap();
+		let facade = auth.encode(&Uuid128::empty()).unwrap();
+		assert_eq!(
+			other.decode(&facade),
+			Err(AuthenticatedFacadeError::InvalidTag)
+		);
+	}
+}
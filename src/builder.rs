@@ -0,0 +1,70 @@
+use crate::uuid::Uuid128;
+
+/// Builder for `UUIDv7` from its individual fields.
+///
+/// The layout follows RFC 9562:
+///
+/// ```text
+/// | unix_ts_ms (48) | ver (4) | rand_a (12) | var (2) | rand_b (62) |
+/// ```
+///
+/// Bits beyond the width of each field are ignored.
+///
+/// # Examples
+/// ```
+/// use uuid47::UuidV7Builder;
+///
+/// let uuid = UuidV7Builder::new()
+///     .timestamp_ms(0x0123_4567_89AB)
+///     .rand_a(0x0CDE)
+///     .rand_b(0x0123_4567_89AB_CDEF)
+///     .build();
+///
+/// assert_eq!(uuid.to_string(), "01234567-89ab-7cde-8123-456789abcdef");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct UuidV7Builder {
+	timestamp_ms: u64,
+	rand_a: u16,
+	rand_b: u64,
+}
+
+impl UuidV7Builder {
+	/// Creates a new builder with all fields set to zero.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the 48-bit Unix timestamp in milliseconds.
+	#[must_use]
+	pub fn timestamp_ms(mut self, timestamp_ms: u64) -> Self {
+		self.timestamp_ms = timestamp_ms;
+		self
+	}
+
+	/// Set the 12-bit `rand_a` field.
+	#[must_use]
+	pub fn rand_a(mut self, rand_a: u16) -> Self {
+		self.rand_a = rand_a;
+		self
+	}
+
+	/// Set the 62-bit `rand_b` field.
+	#[must_use]
+	pub fn rand_b(mut self, rand_b: u64) -> Self {
+		self.rand_b = rand_b;
+		self
+	}
+
+	/// Build the `UUIDv7`.
+	#[must_use]
+	pub fn build(self) -> Uuid128 {
+		let mut out = Uuid128::empty();
+		out.set_timestamp_ms(self.timestamp_ms);
+		out.set_rand_a(self.rand_a);
+		out.set_rand_b(self.rand_b);
+
+		out
+	}
+}
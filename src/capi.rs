@@ -0,0 +1,335 @@
+//! C ABI for the facade transform.
+//!
+//! Build with `--features capi` and `cargo rustc --crate-type staticlib,cdylib` to produce `libuuid47`
+//! (see `capi/Makefile`), the crate itself is only built as a Rust library.
+//! The header is generated into `capi/include/uuid47.h` with `cbindgen`.
+//!
+//! All functions return a [`Uuid47Status`], `UUID47_STATUS_OK` on success.
+//! UUID are passed as 16-byte big-endian buffers.
+
+use core::ffi::c_char;
+
+use crate::error::{UuidParseError, UuidValidationError, WeakKeyError};
+use crate::key::UuidV47Key;
+use crate::sentinel::SentinelPolicy;
+use crate::uuid::Uuid128;
+
+/// Length of a formatted UUID string, excluding the terminating NUL.
+pub const UUID47_STRING_LEN: usize = 36;
+
+/// Status code returned by every C API function.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Uuid47Status {
+	/// Success.
+	Ok = 0,
+
+	/// A required pointer argument was NULL.
+	NullPointer = 1,
+
+	/// The output buffer is too small.
+	BufferTooSmall = 2,
+
+	/// See [`UuidParseError::InvalidLength`].
+	ParseInvalidLength = 10,
+
+	/// See [`UuidParseError::InvalidHex`].
+	ParseInvalidHex = 11,
+
+	/// See [`UuidValidationError::InvalidLength`].
+	InvalidLength = 20,
+
+	/// See [`UuidValidationError::InvalidVersion`].
+	InvalidVersion = 21,
+
+	/// See [`UuidValidationError::InvalidVariant`].
+	InvalidVariant = 22,
+
+	/// See [`UuidValidationError::Sentinel`].
+	Sentinel = 23,
+
+	/// See [`WeakKeyError::Zero`].
+	WeakKeyZero = 30,
+
+	/// See [`WeakKeyError::ExampleKey`].
+	WeakKeyExample = 31,
+
+	/// See [`WeakKeyError::LowEntropy`].
+	WeakKeyLowEntropy = 32,
+}
+
+impl From<UuidParseError> for Uuid47Status {
+	fn from(err: UuidParseError) -> Self {
+		match err {
+			UuidParseError::InvalidLength => Uuid47Status::ParseInvalidLength,
+			UuidParseError::InvalidHex => Uuid47Status::ParseInvalidHex,
+		}
+	}
+}
+
+impl From<UuidValidationError> for Uuid47Status {
+	fn from(err: UuidValidationError) -> Self {
+		match err {
+			UuidValidationError::InvalidLength => Uuid47Status::InvalidLength,
+			UuidValidationError::InvalidVersion => Uuid47Status::InvalidVersion,
+			UuidValidationError::InvalidVariant => Uuid47Status::InvalidVariant,
+			UuidValidationError::Sentinel => Uuid47Status::Sentinel,
+		}
+	}
+}
+
+impl From<WeakKeyError> for Uuid47Status {
+	fn from(err: WeakKeyError) -> Self {
+		match err {
+			WeakKeyError::Zero => Uuid47Status::WeakKeyZero,
+			WeakKeyError::ExampleKey => Uuid47Status::WeakKeyExample,
+			WeakKeyError::LowEntropy => Uuid47Status::WeakKeyLowEntropy,
+		}
+	}
+}
+
+/// Key for `UUIDv47` encoding/decoding.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Uuid47Key {
+	/// First 64 bits of the key.
+	pub k0: u64,
+
+	/// Second 64 bits of the key.
+	pub k1: u64,
+}
+
+impl From<Uuid47Key> for UuidV47Key {
+	fn from(key: Uuid47Key) -> Self {
+		UuidV47Key::new(key.k0, key.k1)
+	}
+}
+
+/// Unwrap a `Result`, returning the error as `Uuid47Status`.
+macro_rules! try_status {
+	($expr:expr) => {
+		match $expr {
+			Ok(value) => value,
+			Err(err) => return Uuid47Status::from(err),
+		}
+	};
+}
+
+/// Check the key unless `allow_weak_key`, then write it to `out`.
+fn checked_key(key: Uuid47Key, allow_weak_key: bool, out: &mut Uuid47Key) -> Uuid47Status {
+	if !allow_weak_key {
+		try_status!(UuidV47Key::from(key).validate());
+	}
+
+	*out = key;
+	Uuid47Status::Ok
+}
+
+/// Creates a key from two 64-bit halves.
+///
+/// Weak keys (see `UuidV47Key::validate`) are refused unless `allow_weak_key` is true.
+///
+/// # Safety
+///
+/// `out` must point to a writable `Uuid47Key`.
+#[no_mangle]
+pub unsafe extern "C" fn uuid47_key_new(
+	k0: u64,
+	k1: u64,
+	allow_weak_key: bool,
+	out: *mut Uuid47Key,
+) -> Uuid47Status {
+	if out.is_null() {
+		return Uuid47Status::NullPointer;
+	}
+
+	checked_key(Uuid47Key { k0, k1 }, allow_weak_key, &mut *out)
+}
+
+/// Creates a key from 16 bytes (`k0` then `k1`, each little-endian).
+///
+/// Weak keys (see `UuidV47Key::validate`) are refused unless `allow_weak_key` is true.
+///
+/// # Safety
+///
+/// `bytes` must point to 16 readable bytes and `out` to a writable `Uuid47Key`.
+#[no_mangle]
+pub unsafe extern "C" fn uuid47_key_from_bytes(
+	bytes: *const u8,
+	allow_weak_key: bool,
+	out: *mut Uuid47Key,
+) -> Uuid47Status {
+	if bytes.is_null() || out.is_null() {
+		return Uuid47Status::NullPointer;
+	}
+
+	let bytes = &*bytes.cast::<[u8; 16]>();
+	let key = Uuid47Key {
+		k0: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+		k1: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+	};
+
+	checked_key(key, allow_weak_key, &mut *out)
+}
+
+/// Encodes a `UUIDv7` into `UUIDv4` facade.
+///
+/// The input is validated, the Nil and Max UUID are passed through unchanged.
+///
+/// # Safety
+///
+/// `v7` and `out` must point to 16 bytes, `key` to a valid `Uuid47Key`.
+#[no_mangle]
+pub unsafe extern "C" fn uuid47_encode(
+	v7: *const u8,
+	key: *const Uuid47Key,
+	out: *mut u8,
+) -> Uuid47Status {
+	if v7.is_null() || key.is_null() || out.is_null() {
+		return Uuid47Status::NullPointer;
+	}
+
+	let v7 = try_status!(Uuid128::from_bytes(*v7.cast::<[u8; 16]>()));
+	let facade = try_status!(
+		v7.checked_encode_as_v4facade(&UuidV47Key::from(*key), SentinelPolicy::Passthrough)
+	);
+	*out.cast::<[u8; 16]>() = facade.into_bytes();
+
+	Uuid47Status::Ok
+}
+
+/// Decodes a `UUIDv4` facade back into `UUIDv7`.
+///
+/// The input is validated, the Nil and Max UUID are passed through unchanged.
+///
+/// # Safety
+///
+/// `facade` and `out` must point to 16 bytes, `key` to a valid `Uuid47Key`.
+#[no_mangle]
+pub unsafe extern "C" fn uuid47_decode(
+	facade: *const u8,
+	key: *const Uuid47Key,
+	out: *mut u8,
+) -> Uuid47Status {
+	if facade.is_null() || key.is_null() || out.is_null() {
+		return Uuid47Status::NullPointer;
+	}
+
+	let facade = try_status!(Uuid128::from_bytes(*facade.cast::<[u8; 16]>()));
+	let v7 =
+		try_status!(facade
+			.checked_decode_from_v4facade(&UuidV47Key::from(*key), SentinelPolicy::Passthrough));
+	*out.cast::<[u8; 16]>() = v7.into_bytes();
+
+	Uuid47Status::Ok
+}
+
+/// Parses a UUID string of `len` bytes (not necessarily NUL-terminated) into 16 bytes.
+///
+/// # Safety
+///
+/// `s` must point to `len` readable bytes and `out` to 16 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn uuid47_parse(s: *const c_char, len: usize, out: *mut u8) -> Uuid47Status {
+	if s.is_null() || out.is_null() {
+		return Uuid47Status::NullPointer;
+	}
+
+	let bytes = core::slice::from_raw_parts(s.cast::<u8>(), len);
+	let s = try_status!(core::str::from_utf8(bytes).map_err(|_| UuidParseError::InvalidHex));
+	let uuid: Uuid128 = try_status!(s.parse());
+	*out.cast::<[u8; 16]>() = uuid.into_bytes();
+
+	Uuid47Status::Ok
+}
+
+/// Formats 16 bytes as a NUL-terminated UUID string.
+///
+/// `buf_len` must be at least `UUID47_STRING_LEN + 1`.
+///
+/// # Safety
+///
+/// `uuid` must point to 16 readable bytes and `buf` to `buf_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn uuid47_format(
+	uuid: *const u8,
+	buf: *mut c_char,
+	buf_len: usize,
+) -> Uuid47Status {
+	if uuid.is_null() || buf.is_null() {
+		return Uuid47Status::NullPointer;
+	}
+	if buf_len < UUID47_STRING_LEN + 1 {
+		return Uuid47Status::BufferTooSmall;
+	}
+
+	let uuid = try_status!(Uuid128::from_bytes(*uuid.cast::<[u8; 16]>()));
+	let s = uuid.to_string();
+
+	let buf = core::slice::from_raw_parts_mut(buf.cast::<u8>(), UUID47_STRING_LEN + 1);
+	buf[..UUID47_STRING_LEN].copy_from_slice(s.as_bytes());
+	buf[UUID47_STRING_LEN] = 0;
+
+	Uuid47Status::Ok
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_capi_roundtrip() {
+		let mut key = Uuid47Key { k0: 0, k1: 0 };
+		let s = "00000000-0000-7000-8000-000000000000";
+
+		let mut v7 = [0u8; 16];
+		let mut facade = [0u8; 16];
+		let mut back = [0u8; 16];
+		let mut buf = [0 as c_char; UUID47_STRING_LEN + 1];
+
+		unsafe {
+			assert_eq!(
+				uuid47_key_new(0x0123456789abcdef, 0xfedcba9876543210, false, &mut key),
+				Uuid47Status::WeakKeyExample
+			);
+			assert_eq!(
+				uuid47_key_new(0x0123456789abcdef, 0xfedcba9876543210, true, &mut key),
+				Uuid47Status::Ok
+			);
+
+			assert_eq!(
+				uuid47_parse(s.as_ptr().cast(), s.len(), v7.as_mut_ptr()),
+				Uuid47Status::Ok
+			);
+			assert_eq!(
+				uuid47_encode(v7.as_ptr(), &key, facade.as_mut_ptr()),
+				Uuid47Status::Ok
+			);
+			assert_eq!(
+				uuid47_format(facade.as_ptr(), buf.as_mut_ptr(), buf.len()),
+				Uuid47Status::Ok
+			);
+			assert_eq!(
+				uuid47_decode(facade.as_ptr(), &key, back.as_mut_ptr()),
+				Uuid47Status::Ok
+			);
+
+			assert_eq!(
+				uuid47_encode(facade.as_ptr(), &key, back.as_mut_ptr()),
+				Uuid47Status::InvalidVersion
+			);
+			assert_eq!(
+				uuid47_format(facade.as_ptr(), buf.as_mut_ptr(), UUID47_STRING_LEN),
+				Uuid47Status::BufferTooSmall
+			);
+			assert_eq!(
+				uuid47_parse(s.as_ptr().cast(), s.len() - 1, v7.as_mut_ptr()),
+				Uuid47Status::ParseInvalidLength
+			);
+		}
+
+		let formatted: Vec<u8> = buf[..UUID47_STRING_LEN].iter().map(|&c| c as u8).collect();
+		assert_eq!(formatted, b"22d97126-9609-4000-8000-000000000000");
+		assert_eq!(back, v7);
+	}
+}
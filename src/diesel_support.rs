@@ -0,0 +1,56 @@
+//! [`diesel`] integration: `Uuid128` as `Binary` on every backend, and as Postgres `Uuid`.
+//!
+//! Values are validated with [`Uuid128::from_bytes`](crate::Uuid128::from_bytes).
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Binary;
+
+use crate::uuid::Uuid128;
+
+impl<DB> ToSql<Binary, DB> for Uuid128
+where
+	DB: Backend,
+	[u8]: ToSql<Binary, DB>,
+{
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+		<[u8] as ToSql<Binary, DB>>::to_sql(self.as_bytes().as_slice(), out)
+	}
+}
+
+impl<DB> FromSql<Binary, DB> for Uuid128
+where
+	DB: Backend,
+	Vec<u8>: FromSql<Binary, DB>,
+{
+	fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+		let bytes = <Vec<u8> as FromSql<Binary, DB>>::from_sql(bytes)?;
+		Ok(Uuid128::try_from(bytes.as_slice())?)
+	}
+}
+
+#[cfg(feature = "diesel-postgres")]
+mod postgres {
+	use std::io::Write;
+
+	use diesel::deserialize::{self, FromSql};
+	use diesel::pg::{Pg, PgValue};
+	use diesel::serialize::{self, IsNull, Output, ToSql};
+	use diesel::sql_types::Uuid;
+
+	use crate::uuid::Uuid128;
+
+	impl ToSql<Uuid, Pg> for Uuid128 {
+		fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+			out.write_all(self.as_bytes())?;
+			Ok(IsNull::No)
+		}
+	}
+
+	impl FromSql<Uuid, Pg> for Uuid128 {
+		fn from_sql(value: PgValue<'_>) -> deserialize::Result<Self> {
+			Ok(Uuid128::try_from(value.as_bytes())?)
+		}
+	}
+}
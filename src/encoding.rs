@@ -0,0 +1,318 @@
+use crate::error::{Base32Error, Base58Error, Base64UrlError};
+use crate::uuid::Uuid128;
+
+/// Crockford base32 alphabet, in ASCII order.
+const BASE32_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Bitcoin base58 alphabet, in ASCII order.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// base64url alphabet (RFC 4648 §5).
+const BASE64URL_ALPHABET: &[u8; 64] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Reverse lookup table, `0xff` for characters outside the alphabet.
+const fn decode_table<const N: usize>(alphabet: &[u8; N]) -> [u8; 256] {
+	let mut table = [0xff; 256];
+	let mut i = 0;
+	while i < N {
+		table[alphabet[i] as usize] = i as u8;
+		i += 1;
+	}
+	table
+}
+
+const BASE32_DECODE: [u8; 256] = decode_table(BASE32_ALPHABET);
+const BASE58_DECODE: [u8; 256] = decode_table(BASE58_ALPHABET);
+const BASE64URL_DECODE: [u8; 256] = decode_table(BASE64URL_ALPHABET);
+
+/// Length of a Crockford base32 UUID string.
+pub const BASE32_LEN: usize = 26;
+
+/// Length of a base58 UUID string.
+pub const BASE58_LEN: usize = 22;
+
+/// Length of a base64url UUID string.
+pub const BASE64URL_LEN: usize = 22;
+
+/// 58^10, the largest power of 58 used to split the value into `u64` chunks.
+const BASE58_CHUNK: u128 = 430_804_206_899_405_824;
+
+fn ascii_string(out: &[u8]) -> String {
+	// safe: we only put ASCII characters of the alphabets
+	unsafe { std::str::from_utf8_unchecked(out) }.to_owned()
+}
+
+/// Parse 26 uppercase Crockford base32 characters into a 128-bit value.
+pub(crate) fn parse_base32(s: &[u8]) -> Result<u128, Base32Error> {
+	if s.len() != BASE32_LEN {
+		return Err(Base32Error::InvalidLength);
+	}
+
+	let mut value = 0u128;
+	for &c in s {
+		let digit = BASE32_DECODE[c as usize];
+		if digit == 0xff {
+			return Err(Base32Error::InvalidCharacter);
+		}
+		value = (value << 5) | u128::from(digit);
+	}
+
+	if BASE32_DECODE[s[0] as usize] > 7 {
+		return Err(Base32Error::Overflow);
+	}
+
+	Ok(value)
+}
+
+impl Uuid128 {
+	/// Encode into 26-character Crockford base32 (ULID-style, uppercase).
+	///
+	/// The alphabet is in ASCII order, so the strings sort like the UUID (time-ordered for `UUIDv7`).
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::empty();
+	/// assert_eq!(uuid.to_base32(), "0000000000E008000000000000");
+	/// assert_eq!(uuid47::Uuid128::from_base32(&uuid.to_base32()), Ok(uuid));
+	/// ```
+	#[must_use]
+	pub fn to_base32(&self) -> String {
+		let value = self.to_u128();
+		let mut out = [0u8; BASE32_LEN];
+
+		// 130 bits, the first character only carries 3
+		for (i, c) in out.iter_mut().enumerate() {
+			let shift = 5 * (BASE32_LEN - 1 - i);
+			*c = BASE32_ALPHABET[((value >> shift) & 0x1f) as usize];
+		}
+
+		ascii_string(&out)
+	}
+
+	/// Decode from 26-character Crockford base32.
+	///
+	/// Only the canonical uppercase form produced by [`Uuid128::to_base32`] is accepted,
+	/// so every UUID has exactly one base32 string.
+	///
+	/// # Errors
+	///
+	/// * [`Base32Error::InvalidLength`] - if the string is not 26 characters long.
+	/// * [`Base32Error::InvalidCharacter`] - if a character is outside the uppercase Crockford alphabet.
+	/// * [`Base32Error::Overflow`] - if the first character is greater than `7`.
+	/// * [`Base32Error::InvalidUuid`] - if the bytes are not a valid UUID (see [`Uuid128::from_bytes`]).
+	pub fn from_base32(s: &str) -> Result<Self, Base32Error> {
+		let value = parse_base32(s.as_bytes())?;
+
+		Self::try_from(value).map_err(|_| Base32Error::InvalidUuid)
+	}
+
+	/// Encode into 22-character base58 (Bitcoin alphabet), left-padded with `1`.
+	///
+	/// The alphabet is in ASCII order and the width is fixed, so the strings sort like the UUID.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::empty();
+	/// assert_eq!(uuid.to_base58(), "111111117Hxt1fAWDjN7TV");
+	/// assert_eq!(uuid47::Uuid128::from_base58(&uuid.to_base58()), Ok(uuid));
+	/// ```
+	#[must_use]
+	pub fn to_base58(&self) -> String {
+		let value = self.to_u128();
+		let mut out = [BASE58_ALPHABET[0]; BASE58_LEN];
+
+		// two u128 divisions, then 10 + 10 + 2 digits in u64 arithmetic
+		let high = value / BASE58_CHUNK;
+		let chunks = [
+			(high / BASE58_CHUNK) as u64,
+			(high % BASE58_CHUNK) as u64,
+			(value % BASE58_CHUNK) as u64,
+		];
+		for (digits, mut chunk) in out.rchunks_mut(10).zip(chunks.into_iter().rev()) {
+			for c in digits.iter_mut().rev() {
+				*c = BASE58_ALPHABET[(chunk % 58) as usize];
+				chunk /= 58;
+			}
+		}
+
+		ascii_string(&out)
+	}
+
+	/// Decode from 22-character base58 (Bitcoin alphabet).
+	///
+	/// # Errors
+	///
+	/// * [`Base58Error::InvalidLength`] - if the string is not 22 characters long.
+	/// * [`Base58Error::InvalidCharacter`] - if a character is outside the base58 alphabet.
+	/// * [`Base58Error::Overflow`] - if the value does not fit in 128 bits.
+	/// * [`Base58Error::InvalidUuid`] - if the bytes are not a valid UUID (see [`Uuid128::from_bytes`]).
+	pub fn from_base58(s: &str) -> Result<Self, Base58Error> {
+		let s = s.as_bytes();
+		if s.len() != BASE58_LEN {
+			return Err(Base58Error::InvalidLength);
+		}
+
+		let mut value = 0u128;
+		for &c in s {
+			let digit = BASE58_DECODE[c as usize];
+			if digit == 0xff {
+				return Err(Base58Error::InvalidCharacter);
+			}
+			value = value
+				.checked_mul(58)
+				.and_then(|value| value.checked_add(u128::from(digit)))
+				.ok_or(Base58Error::Overflow)?;
+		}
+
+		Self::try_from(value).map_err(|_| Base58Error::InvalidUuid)
+	}
+
+	/// Encode into 22-character base64url (RFC 4648 §5), without padding.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::empty();
+	/// assert_eq!(uuid.to_base64url(), "AAAAAAAAcACAAAAAAAAAAA");
+	/// assert_eq!(uuid47::Uuid128::from_base64url(&uuid.to_base64url()), Ok(uuid));
+	/// ```
+	#[must_use]
+	pub fn to_base64url(&self) -> String {
+		let value = self.to_u128();
+		let mut out = [0u8; BASE64URL_LEN];
+
+		// 132 bits, the last character only carries 2
+		let (head, last) = out.split_at_mut(BASE64URL_LEN - 1);
+		for (i, c) in head.iter_mut().enumerate() {
+			let shift = 2 + 6 * (BASE64URL_LEN - 2 - i);
+			*c = BASE64URL_ALPHABET[((value >> shift) & 0x3f) as usize];
+		}
+		last[0] = BASE64URL_ALPHABET[((value & 0x3) << 4) as usize];
+
+		ascii_string(&out)
+	}
+
+	/// Decode from 22-character base64url (RFC 4648 §5), without padding.
+	///
+	/// # Errors
+	///
+	/// * [`Base64UrlError::InvalidLength`] - if the string is not 22 characters long.
+	/// * [`Base64UrlError::InvalidCharacter`] - if a character is outside the base64url alphabet.
+	/// * [`Base64UrlError::NonCanonical`] - if the unused bits of the last character are not zero.
+	/// * [`Base64UrlError::InvalidUuid`] - if the bytes are not a valid UUID (see [`Uuid128::from_bytes`]).
+	pub fn from_base64url(s: &str) -> Result<Self, Base64UrlError> {
+		let s = s.as_bytes();
+		if s.len() != BASE64URL_LEN {
+			return Err(Base64UrlError::InvalidLength);
+		}
+
+		let mut value = 0u128;
+		for &c in &s[..BASE64URL_LEN - 1] {
+			let digit = BASE64URL_DECODE[c as usize];
+			if digit == 0xff {
+				return Err(Base64UrlError::InvalidCharacter);
+			}
+			value = (value << 6) | u128::from(digit);
+		}
+
+		let last = BASE64URL_DECODE[s[BASE64URL_LEN - 1] as usize];
+		if last == 0xff {
+			return Err(Base64UrlError::InvalidCharacter);
+		}
+		if last & 0xf != 0 {
+			return Err(Base64UrlError::NonCanonical);
+		}
+		value = (value << 2) | u128::from(last >> 4);
+
+		Self::try_from(value).map_err(|_| Base64UrlError::InvalidUuid)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::builder::UuidV7Builder;
+
+	#[test]
+	fn test_roundtrip_and_sorting() {
+		let mut uuids: Vec<Uuid128> = (0..64u64)
+			.map(|i| {
+				UuidV7Builder::new()
+					.timestamp_ms(i.wrapping_mul(0x9e37_79b9_7f4a) >> 16)
+					.rand_a(i.wrapping_mul(31) as u16)
+					.rand_b(i.wrapping_mul(0x2545_f491_4f6c_dd1d))
+					.build()
+			})
+			.collect();
+		uuids.extend([Uuid128::nil(), Uuid128::max()]);
+
+		for uuid in &uuids {
+			assert_eq!(Uuid128::from_base32(&uuid.to_base32()), Ok(*uuid));
+			assert_eq!(Uuid128::from_base58(&uuid.to_base58()), Ok(*uuid));
+			assert_eq!(Uuid128::from_base64url(&uuid.to_base64url()), Ok(*uuid));
+		}
+
+		uuids.sort();
+		let base32: Vec<String> = uuids.iter().map(Uuid128::to_base32).collect();
+		let base58: Vec<String> = uuids.iter().map(Uuid128::to_base58).collect();
+		assert!(base32.windows(2).all(|w| w[0] < w[1]));
+		assert!(base58.windows(2).all(|w| w[0] < w[1]));
+
+		assert_eq!(Uuid128::max().to_base32(), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+		assert_eq!(Uuid128::max().to_base58(), "YcVfxkQb6JRzqk5kF2tNLv");
+		assert_eq!(Uuid128::max().to_base64url(), "_____________________w");
+	}
+
+	#[test]
+	fn test_strict_decoding() {
+		let uuid = Uuid128::empty();
+
+		assert_eq!(
+			Uuid128::from_base32("0000000000E00800000000000"),
+			Err(Base32Error::InvalidLength)
+		);
+		assert_eq!(
+			Uuid128::from_base32("0000000000e008000000000000"),
+			Err(Base32Error::InvalidCharacter)
+		);
+		assert_eq!(
+			Uuid128::from_base32("8ZZZZZZZZZZZZZZZZZZZZZZZZZ"),
+			Err(Base32Error::Overflow)
+		);
+		// version 0
+		assert_eq!(
+			Uuid128::from_base32("00000000000000000000000001"),
+			Err(Base32Error::InvalidUuid)
+		);
+
+		assert_eq!(
+			Uuid128::from_base58(&uuid.to_base58()[1..]),
+			Err(Base58Error::InvalidLength)
+		);
+		assert_eq!(
+			Uuid128::from_base58("011111117Hxt1fAWDjN7TV"),
+			Err(Base58Error::InvalidCharacter)
+		);
+		assert_eq!(
+			Uuid128::from_base58("zzzzzzzzzzzzzzzzzzzzzz"),
+			Err(Base58Error::Overflow)
+		);
+
+		assert_eq!(
+			Uuid128::from_base64url("AAAAAAAAcACAAAAAAAAAAA=="),
+			Err(Base64UrlError::InvalidLength)
+		);
+		assert_eq!(
+			Uuid128::from_base64url("AAAAAAAAcACAAAAAAAAAA+"),
+			Err(Base64UrlError::InvalidCharacter)
+		);
+		assert_eq!(
+			Uuid128::from_base64url("AAAAAAAAcACAAAAAAAAAAB"),
+			Err(Base64UrlError::NonCanonical)
+		);
+		assert_eq!(
+			Uuid128::from_base64url("AAAAAAAAAAAAAAAAAAAAAQ"),
+			Err(Base64UrlError::InvalidUuid)
+		);
+	}
+}
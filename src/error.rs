@@ -1,3 +1,5 @@
+use crate::loader::KeySource;
+
 /// An error which could be returned when parsing `Uuid128`.
 ///
 /// This occurs when the `FromStr` implementation of `Uuid128` fails.
@@ -26,8 +28,15 @@ impl std::fmt::Display for UuidParseError {
 impl std::error::Error for UuidParseError {}
 
 /// Error type representing a failure to validate bytes as a UUID."
+///
+/// New variants may be added in minor releases.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum UuidValidationError {
+	/// The input byte slice length is invalid.<br>
+	/// A valid UUID should be 16 bytes long.
+	InvalidLength,
+
 	/// The input bytes do not represent a valid UUID version<br>
 	/// Valid versions are 4 (random) and 7 (time-ordered).
 	InvalidVersion,
@@ -35,19 +44,544 @@ pub enum UuidValidationError {
 	/// The input bytes do not represent a valid UUID variant<br>
 	/// Valid variant is RFC 4122 (the variant used by `UUIDv4` and `UUIDv7`).
 	InvalidVariant,
+
+	/// The input is the Nil or Max UUID, which the configured
+	/// [`SentinelPolicy`](crate::SentinelPolicy) rejects.
+	Sentinel,
 }
 
 impl std::fmt::Display for UuidValidationError {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
+			UuidValidationError::InvalidLength => {
+				write!(f, "Invalid length for UUID bytes (should be 16)")
+			}
 			UuidValidationError::InvalidVersion => {
 				write!(f, "Invalid version in UUID bytes. Must be 4 or 7")
 			}
 			UuidValidationError::InvalidVariant => {
 				write!(f, "Invalid variant in UUID bytes. Must be RFC 4122 variant")
 			}
+			UuidValidationError::Sentinel => {
+				write!(f, "Nil or Max UUID is not allowed here")
+			}
 		}
 	}
 }
 
 impl std::error::Error for UuidValidationError {}
+
+/// An error which could be returned when decoding `Uuid128` from Crockford base32.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum Base32Error {
+	/// The input string length is invalid.<br>
+	/// A valid base32 UUID should be 26 characters long.
+	InvalidLength,
+
+	/// The input string contains a character outside the uppercase Crockford alphabet.
+	InvalidCharacter,
+
+	/// The first character is greater than `7`, the value does not fit in 128 bits.
+	Overflow,
+
+	/// The decoded bytes are not a valid UUID (see [`UuidValidationError`]).
+	InvalidUuid,
+}
+
+impl std::fmt::Display for Base32Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Base32Error::InvalidLength => {
+				write!(f, "Invalid length for base32 UUID string (should be 26)")
+			}
+			Base32Error::InvalidCharacter => {
+				write!(f, "Invalid character in base32 UUID string")
+			}
+			Base32Error::Overflow => write!(f, "Base32 UUID string overflows 128 bits"),
+			Base32Error::InvalidUuid => write!(f, "Base32 string is not a valid UUID"),
+		}
+	}
+}
+
+impl std::error::Error for Base32Error {}
+
+/// An error which could be returned by `Uuid128::try_from_ulid`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum UlidError {
+	/// Bits 76-79 or 62-63 of the ULID are set, they would be overwritten by the `UUIDv7` version and variant.
+	OverwrittenBits,
+}
+
+impl std::fmt::Display for UlidError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			UlidError::OverwrittenBits => {
+				write!(f, "ULID bits 76-79 and 62-63 must be clear")
+			}
+		}
+	}
+}
+
+impl std::error::Error for UlidError {}
+
+/// An error which could be returned by [`test_vectors`](crate::test_vectors).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TestVectorError {
+	/// The line (1-based) of the vector file is not `k0,k1,v7,facade`.
+	Malformed(usize),
+}
+
+impl std::fmt::Display for TestVectorError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			TestVectorError::Malformed(line) => write!(f, "Malformed test vector at line {line}"),
+		}
+	}
+}
+
+impl std::error::Error for TestVectorError {}
+
+/// An error which could be returned when decoding `Uuid128` from base58.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum Base58Error {
+	/// The input string length is invalid.<br>
+	/// A valid base58 UUID should be 22 characters long.
+	InvalidLength,
+
+	/// The input string contains a character outside the Bitcoin base58 alphabet.
+	InvalidCharacter,
+
+	/// The value does not fit in 128 bits.
+	Overflow,
+
+	/// The decoded bytes are not a valid UUID (see [`UuidValidationError`]).
+	InvalidUuid,
+}
+
+impl std::fmt::Display for Base58Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Base58Error::InvalidLength => {
+				write!(f, "Invalid length for base58 UUID string (should be 22)")
+			}
+			Base58Error::InvalidCharacter => {
+				write!(f, "Invalid character in base58 UUID string")
+			}
+			Base58Error::Overflow => write!(f, "Base58 UUID string overflows 128 bits"),
+			Base58Error::InvalidUuid => write!(f, "Base58 string is not a valid UUID"),
+		}
+	}
+}
+
+impl std::error::Error for Base58Error {}
+
+/// An error which could be returned when decoding `Uuid128` from base64url.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum Base64UrlError {
+	/// The input string length is invalid.<br>
+	/// A valid base64url UUID should be 22 characters long, without padding.
+	InvalidLength,
+
+	/// The input string contains a character outside the base64url alphabet.
+	InvalidCharacter,
+
+	/// The unused low 4 bits of the last character are not zero.
+	NonCanonical,
+
+	/// The decoded bytes are not a valid UUID (see [`UuidValidationError`]).
+	InvalidUuid,
+}
+
+impl std::fmt::Display for Base64UrlError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Base64UrlError::InvalidLength => {
+				write!(f, "Invalid length for base64url UUID string (should be 22)")
+			}
+			Base64UrlError::InvalidCharacter => {
+				write!(f, "Invalid character in base64url UUID string")
+			}
+			Base64UrlError::NonCanonical => {
+				write!(f, "Non-canonical trailing bits in base64url UUID string")
+			}
+			Base64UrlError::InvalidUuid => write!(f, "Base64url string is not a valid UUID"),
+		}
+	}
+}
+
+impl std::error::Error for Base64UrlError {}
+
+/// An error which could be returned when creating or parsing a [`PrefixedId`](crate::PrefixedId).
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum PrefixedIdError {
+	/// The prefix is not 0 to 63 lowercase ASCII letters and underscores,
+	/// starting and ending with a letter.
+	InvalidPrefix,
+
+	/// The suffix is not a valid lowercase base32 UUID.
+	InvalidSuffix(Base32Error),
+
+	/// The prefix is not the expected one.
+	PrefixMismatch,
+
+	/// No key is registered for the prefix.
+	UnknownPrefix,
+
+	/// The UUID is not a `UUIDv4` facade.
+	NotFacade,
+}
+
+impl std::fmt::Display for PrefixedIdError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			PrefixedIdError::InvalidPrefix => write!(f, "Invalid prefix in prefixed ID"),
+			PrefixedIdError::InvalidSuffix(err) => {
+				write!(f, "Invalid suffix in prefixed ID: {err}")
+			}
+			PrefixedIdError::PrefixMismatch => write!(f, "Unexpected prefix in prefixed ID"),
+			PrefixedIdError::UnknownPrefix => write!(f, "Unknown prefix in prefixed ID"),
+			PrefixedIdError::NotFacade => write!(f, "Prefixed ID is not a UUIDv4 facade"),
+		}
+	}
+}
+
+impl std::error::Error for PrefixedIdError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			PrefixedIdError::InvalidSuffix(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+/// An error which could be returned when parsing or verifying a
+/// [`MappingTable`](crate::MappingTable).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum MappingTableError {
+	/// The first line is not the `legacy,v7` header.
+	InvalidHeader,
+
+	/// The line (1-based) is not a `legacy,v7` pair of valid UUID.
+	InvalidLine(usize),
+
+	/// The entry (1-based) is not derived from its legacy UUID with the given key.
+	Mismatch(usize),
+}
+
+impl std::fmt::Display for MappingTableError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			MappingTableError::InvalidHeader => {
+				write!(f, "Invalid mapping table header (should be \"legacy,v7\")")
+			}
+			MappingTableError::InvalidLine(line) => {
+				write!(f, "Invalid mapping table line {line}")
+			}
+			MappingTableError::Mismatch(entry) => {
+				write!(f, "Mapping table entry {entry} does not match the key")
+			}
+		}
+	}
+}
+
+impl std::error::Error for MappingTableError {}
+
+/// An error which could be returned when encoding or decoding a `UUIDv8` facade.
+#[cfg(feature = "v8-facade")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v8-facade")))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum V8FacadeError {
+	/// The input is not a `UUIDv7` (encoding) or a `UUIDv8` facade (decoding).
+	InvalidVersion,
+
+	/// The key ID does not fit in 4 bits.
+	InvalidKeyId,
+
+	/// No key is registered for the key ID of the facade.
+	UnknownKeyId,
+
+	/// The timestamp does not fit in 44 bits (after year 2527).
+	TimestampOverflow,
+}
+
+#[cfg(feature = "v8-facade")]
+impl std::fmt::Display for V8FacadeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			V8FacadeError::InvalidVersion => write!(f, "Invalid version for UUIDv8 facade"),
+			V8FacadeError::InvalidKeyId => write!(f, "Key ID must be less than 16"),
+			V8FacadeError::UnknownKeyId => write!(f, "Unknown key ID in UUIDv8 facade"),
+			V8FacadeError::TimestampOverflow => {
+				write!(f, "Timestamp does not fit in UUIDv8 facade (44 bits)")
+			}
+		}
+	}
+}
+
+#[cfg(feature = "v8-facade")]
+impl std::error::Error for V8FacadeError {}
+
+/// An error which could be returned by [`AuthenticatedFacade`](crate::AuthenticatedFacade).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum AuthenticatedFacadeError {
+	/// The tag size is outside the supported range.
+	InvalidTagBits,
+
+	/// The input is not a `UUIDv7` (encoding) or a `UUIDv4` facade (decoding).
+	InvalidVersion,
+
+	/// The input is the Nil or Max UUID, which cannot carry a tag.
+	Sentinel,
+
+	/// The `UUIDv7` has non-zero bits where the tag goes, see
+	/// [`AuthenticatedFacade::clear_tag_bits`](crate::AuthenticatedFacade::clear_tag_bits).
+	TagBitsNotZero,
+
+	/// The tag does not verify: the facade was forged or tampered with.
+	InvalidTag,
+}
+
+impl std::fmt::Display for AuthenticatedFacadeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			AuthenticatedFacadeError::InvalidTagBits => {
+				write!(f, "Tag size must be between 8 and 48 bits")
+			}
+			AuthenticatedFacadeError::InvalidVersion => {
+				write!(f, "Invalid version for authenticated facade")
+			}
+			AuthenticatedFacadeError::Sentinel => {
+				write!(f, "Nil or Max UUID cannot be authenticated")
+			}
+			AuthenticatedFacadeError::TagBitsNotZero => {
+				write!(f, "UUIDv7 has non-zero bits in the tag position")
+			}
+			AuthenticatedFacadeError::InvalidTag => write!(f, "Invalid facade tag"),
+		}
+	}
+}
+
+impl std::error::Error for AuthenticatedFacadeError {}
+
+/// An error which could be returned by [`UuidV47Key::validate`](crate::UuidV47Key::validate).
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum WeakKeyError {
+	/// The key is all zeros.
+	Zero,
+
+	/// The key is the example key from the documentation.
+	ExampleKey,
+
+	/// The key follows an obvious pattern (repeated or sequential bytes, a zero half, ...).
+	LowEntropy,
+}
+
+impl std::fmt::Display for WeakKeyError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			WeakKeyError::Zero => write!(f, "Key is all zeros"),
+			WeakKeyError::ExampleKey => write!(f, "Key is the documented example key"),
+			WeakKeyError::LowEntropy => write!(f, "Key has low entropy"),
+		}
+	}
+}
+
+impl std::error::Error for WeakKeyError {}
+
+/// An error which could be returned when parsing a [`UuidV47Key`](crate::UuidV47Key) from hex.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum KeyParseError {
+	/// The string is not 32 characters long.
+	InvalidLength,
+
+	/// The string contains a character that is not a hex digit.
+	InvalidCharacter,
+}
+
+impl std::fmt::Display for KeyParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KeyParseError::InvalidLength => write!(f, "Key must be 32 hex digits"),
+			KeyParseError::InvalidCharacter => write!(f, "Key contains a non-hex character"),
+		}
+	}
+}
+
+impl std::error::Error for KeyParseError {}
+
+/// An error which could be returned by [`KeyLoader`](crate::KeyLoader).
+///
+/// Every variant names the source that failed, the key material itself is never included.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum KeyLoadError {
+	/// None of the sources is set.
+	NotFound,
+
+	/// The source exists but could not be read.
+	Io(KeySource, std::io::ErrorKind),
+
+	/// The value is not a valid key.
+	Parse(KeySource, KeyParseError),
+
+	/// The key is rejected by [`UuidV47Key::validate`](crate::UuidV47Key::validate).
+	WeakKey(KeySource, WeakKeyError),
+
+	/// The file is writable by group or others (Unix mode bits).
+	InsecurePermissions(KeySource, u32),
+
+	/// The file is larger than [`KEY_FILE_MAX_LEN`](crate::KEY_FILE_MAX_LEN) bytes.
+	TooLarge(KeySource),
+
+	/// The keyring file is invalid.
+	#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(any(feature = "keyring-json", feature = "keyring-toml")))
+	)]
+	Keyring(KeySource, KeyringError),
+}
+
+impl std::fmt::Display for KeyLoadError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KeyLoadError::NotFound => write!(f, "No key source is set"),
+			KeyLoadError::Io(source, kind) => write!(f, "Cannot read {source}: {kind}"),
+			KeyLoadError::Parse(source, err) => write!(f, "Invalid key in {source}: {err}"),
+			KeyLoadError::WeakKey(source, err) => write!(f, "Weak key in {source}: {err}"),
+			KeyLoadError::InsecurePermissions(source, mode) => {
+				write!(
+					f,
+					"Insecure permissions {mode:o} on {source} (writable by group or others)"
+				)
+			}
+			KeyLoadError::TooLarge(source) => write!(
+				f,
+				"Cannot read {source}: larger than {} bytes",
+				crate::KEY_FILE_MAX_LEN
+			),
+			#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+			KeyLoadError::Keyring(source, err) => write!(f, "Invalid keyring {source}: {err}"),
+		}
+	}
+}
+
+impl std::error::Error for KeyLoadError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			KeyLoadError::Parse(_, err) => Some(err),
+			KeyLoadError::WeakKey(_, err) => Some(err),
+			#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+			KeyLoadError::Keyring(_, err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+/// An error which could be returned when parsing a [`Keyring`](crate::Keyring).
+///
+/// Entries are referred to by their ID, the key material itself is never included.
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "keyring-json", feature = "keyring-toml")))
+)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum KeyringError {
+	/// The file extension is neither `.json` nor `.toml` (or the feature is disabled).
+	UnknownFormat,
+
+	/// The document does not match the keyring format, at the given line (1-based).
+	Syntax(usize),
+
+	/// The keyring has no entry.
+	Empty,
+
+	/// Two entries have the same ID.
+	DuplicateId(String),
+
+	/// No entry is marked as primary.
+	NoPrimary,
+
+	/// More than one entry is marked as primary.
+	MultiplePrimary,
+
+	/// The primary entry is not active yet (its activation date is in the future).
+	PrimaryNotActive(String),
+
+	/// The key of the entry is invalid.
+	InvalidKey(String, KeyParseError),
+
+	/// The key of the entry is rejected by [`UuidV47Key::validate`](crate::UuidV47Key::validate).
+	WeakKey(String, WeakKeyError),
+
+	/// The activation date of the entry is not `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ`.
+	InvalidDate(String),
+}
+
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+impl std::fmt::Display for KeyringError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KeyringError::UnknownFormat => write!(f, "Unknown keyring format"),
+			KeyringError::Syntax(line) => write!(f, "Invalid keyring at line {line}"),
+			KeyringError::Empty => write!(f, "Keyring has no key"),
+			KeyringError::DuplicateId(id) => write!(f, "Duplicate key ID \"{id}\""),
+			KeyringError::NoPrimary => write!(f, "No primary key"),
+			KeyringError::MultiplePrimary => write!(f, "More than one primary key"),
+			KeyringError::PrimaryNotActive(id) => {
+				write!(f, "Primary key \"{id}\" is not active yet")
+			}
+			KeyringError::InvalidKey(id, err) => write!(f, "Key \"{id}\": {err}"),
+			KeyringError::WeakKey(id, err) => write!(f, "Key \"{id}\": {err}"),
+			KeyringError::InvalidDate(id) => write!(f, "Key \"{id}\": invalid activation date"),
+		}
+	}
+}
+
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+impl std::error::Error for KeyringError {}
+
+/// An error which could be returned by [`UuidV47Key::from_passphrase`](crate::UuidV47Key::from_passphrase)
+/// or when parsing [`KdfParams`](crate::KdfParams).
+#[cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum KdfError {
+	/// The algorithm is unknown, or its feature is disabled.
+	UnsupportedAlgorithm,
+
+	/// The parameters are malformed, not canonical, or out of range for the algorithm.
+	InvalidParams,
+
+	/// The parameters are below the minimum cost, see [`KdfParams`](crate::KdfParams).
+	WeakParams,
+
+	/// The salt is shorter than [`KDF_MIN_SALT_LEN`](crate::KDF_MIN_SALT_LEN).
+	SaltTooShort,
+
+	/// The derived key is rejected by [`UuidV47Key::validate`](crate::UuidV47Key::validate).
+	WeakKey(WeakKeyError),
+}
+
+#[cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))]
+impl std::fmt::Display for KdfError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KdfError::UnsupportedAlgorithm => write!(f, "Unsupported KDF algorithm"),
+			KdfError::InvalidParams => write!(f, "Invalid KDF parameters"),
+			KdfError::WeakParams => write!(f, "KDF parameters are below the minimum cost"),
+			KdfError::SaltTooShort => write!(f, "KDF salt must be at least 8 bytes"),
+			KdfError::WeakKey(err) => write!(f, "Derived key is weak: {err}"),
+		}
+	}
+}
+
+#[cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))]
+impl std::error::Error for KdfError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			KdfError::WeakKey(err) => Some(err),
+			_ => None,
+		}
+	}
+}
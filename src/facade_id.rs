@@ -0,0 +1,421 @@
+use std::marker::PhantomData;
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+use std::time::SystemTime;
+
+use crate::error::{UuidParseError, UuidValidationError};
+use crate::key::{FacadeKey, UuidV47Key};
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+use crate::keyring::Keyring;
+use crate::sentinel::SentinelPolicy;
+use crate::uuid::Uuid128;
+
+/// Internal `UUIDv7` decoded from a `UUIDv4` facade in the request path.
+///
+/// Used as an extractor in `axum` and `actix-web` handlers with the [`FacadeKey`] taken from the app state.<br>
+/// The route must have exactly one path parameter, use [`FacadeQuery`] for query parameters.<br>
+/// With a `Keyring` in the app state instead, use `FacadeIdCandidates` (`keyring-json` or `keyring-toml` feature).
+///
+/// * Malformed UUID strings are rejected with `400 Bad Request`.
+/// * Well-formed UUID that are not `UUIDv4` facades (including Nil and Max) are rejected with `404 Not Found`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct FacadeId(pub Uuid128);
+
+impl FacadeId {
+	/// Parse a `UUIDv4` facade string and decode it into the internal `UUIDv7`.
+	///
+	/// Useful to decode facades from bodies or headers with the same rules as the extractor.
+	///
+	/// # Errors
+	///
+	/// * [`FacadeIdRejection::Parse`] - if the string is not a valid UUID.
+	/// * [`FacadeIdRejection::NotFacade`] - if the UUID is not a `UUIDv4` facade.
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{FacadeId, UuidV47Key};
+	///
+	/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	/// let id = FacadeId::decode("22d97126-9609-4000-8000-000000000000", &key).unwrap();
+	/// assert_eq!(id.into_inner().to_string(), "00000000-0000-7000-8000-000000000000");
+	/// ```
+	pub fn decode(facade: &str, key: &UuidV47Key) -> Result<Self, FacadeIdRejection> {
+		let facade: Uuid128 = facade.parse().map_err(FacadeIdRejection::Parse)?;
+		let v7 = facade
+			.checked_decode_from_v4facade(key, SentinelPolicy::Reject)
+			.map_err(FacadeIdRejection::NotFacade)?;
+
+		Ok(Self(v7))
+	}
+
+	/// Get the internal `UUIDv7`.
+	#[must_use]
+	pub fn into_inner(self) -> Uuid128 {
+		self.0
+	}
+}
+
+impl std::ops::Deref for FacadeId {
+	type Target = Uuid128;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+/// Internal `UUIDv7` candidates decoded from a `UUIDv4` facade in the request path with every active key of a [`Keyring`].
+///
+/// Used like [`FacadeId`] with an `Arc<Keyring>` in the app state (`Arc<Keyring>: FromRef<S>` for axum,
+/// `web::Data<Keyring>` for actix-web), so facades issued before a key rotation keep resolving.<br>
+/// The candidates are in the order of the keys: the primary key first, then the other active keys in file order.
+///
+/// A facade decodes to a well-formed `UUIDv7` under any key, so the extractor cannot tell which key issued it:
+/// look the candidates up in order and use the first one that exists.
+/// Each active key costs one SipHash per request and, in the worst case, one more lookup.
+/// Only the timestamp depends on the key, so a candidate of a wrong key matches an existing row
+/// only if that row shares all 74 random bits, about `rows / 2^74` per extra key.<br>
+/// Keep few keys active, or use `UUIDv8` facades (`v8-facade` feature) which carry the key ID.
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "keyring-json", feature = "keyring-toml")))
+)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct FacadeIdCandidates(pub Vec<Uuid128>);
+
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+impl FacadeIdCandidates {
+	/// Parse a `UUIDv4` facade string and decode it with the keys of `keyring` active at `time`.
+	///
+	/// # Errors
+	///
+	/// * [`FacadeIdRejection::Parse`] - if the string is not a valid UUID.
+	/// * [`FacadeIdRejection::NotFacade`] - if the UUID is not a `UUIDv4` facade.
+	pub fn decode(
+		facade: &str,
+		keyring: &Keyring,
+		time: SystemTime,
+	) -> Result<Self, FacadeIdRejection> {
+		let primary = keyring.primary();
+		let keys = std::iter::once(primary).chain(
+			keyring
+				.active_at(time)
+				.filter(|entry| entry.id != primary.id),
+		);
+
+		keys.map(|entry| FacadeId::decode(facade, &entry.key).map(FacadeId::into_inner))
+			.collect::<Result<_, _>>()
+			.map(Self)
+	}
+
+	/// Get the candidates, most likely first.
+	#[must_use]
+	pub fn into_inner(self) -> Vec<Uuid128> {
+		self.0
+	}
+}
+
+/// Name of the query parameter extracted by [`FacadeQuery`].
+pub trait FacadeQueryParam {
+	/// Parameter name, compared with the raw (not percent-decoded) query string.
+	const NAME: &'static str;
+}
+
+/// The `id` query parameter, the default of [`FacadeQuery`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct IdParam;
+
+impl FacadeQueryParam for IdParam {
+	const NAME: &'static str = "id";
+}
+
+/// Internal `UUIDv7` decoded from a `UUIDv4` facade in a query parameter.
+///
+/// Same as [`FacadeId`] for the first query parameter named `P::NAME` (`id` by default).<br>
+/// A missing parameter is rejected with `400 Bad Request`.
+///
+/// # Examples
+/// ```
+/// use uuid47::{FacadeQuery, FacadeQueryParam};
+///
+/// struct ItemId;
+///
+/// impl FacadeQueryParam for ItemId {
+///     const NAME: &'static str = "item_id";
+/// }
+///
+/// // GET /items?item_id=<facade>
+/// async fn get_item(id: FacadeQuery<ItemId>) -> String {
+///     id.to_string()
+/// }
+/// ```
+pub struct FacadeQuery<P = IdParam>(pub Uuid128, PhantomData<P>);
+
+impl<P: FacadeQueryParam> FacadeQuery<P> {
+	/// Find the parameter in a query string (without `?`) and decode it like [`FacadeId::decode`].
+	///
+	/// # Errors
+	///
+	/// * [`FacadeIdRejection::MissingQueryParam`] - if the parameter is not in the query string.
+	/// * [`FacadeIdRejection::Parse`] - if the value is not a valid UUID.
+	/// * [`FacadeIdRejection::NotFacade`] - if the UUID is not a `UUIDv4` facade.
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{FacadeQuery, UuidV47Key};
+	///
+	/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	/// let id: FacadeQuery = FacadeQuery::decode("page=2&id=22d97126-9609-4000-8000-000000000000", &key).unwrap();
+	/// assert_eq!(id.into_inner().to_string(), "00000000-0000-7000-8000-000000000000");
+	/// ```
+	pub fn decode(query: &str, key: &UuidV47Key) -> Result<Self, FacadeIdRejection> {
+		let facade = query
+			.split('&')
+			.find_map(|pair| pair.strip_prefix(P::NAME)?.strip_prefix('='))
+			.ok_or(FacadeIdRejection::MissingQueryParam(P::NAME))?;
+
+		FacadeId::decode(facade, key).map(|id| Self(id.0, PhantomData))
+	}
+
+	/// Get the internal `UUIDv7`.
+	#[must_use]
+	pub fn into_inner(self) -> Uuid128 {
+		self.0
+	}
+}
+
+impl<P> std::ops::Deref for FacadeQuery<P> {
+	type Target = Uuid128;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<P> std::fmt::Debug for FacadeQuery<P> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("FacadeQuery").field(&self.0).finish()
+	}
+}
+
+impl<P> Clone for FacadeQuery<P> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<P> Copy for FacadeQuery<P> {}
+
+impl<P> PartialEq for FacadeQuery<P> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<P> Eq for FacadeQuery<P> {}
+
+/// Rejection returned by the [`FacadeId`] and [`FacadeQuery`] extractors.
+#[derive(Debug)]
+pub enum FacadeIdRejection {
+	/// The route does not have exactly one path parameter (`500 Internal Server Error`).
+	MissingPathParam,
+
+	/// The query parameter of a [`FacadeQuery`] is missing (`400 Bad Request`).
+	MissingQueryParam(&'static str),
+
+	/// No [`FacadeKey`] (or `Keyring` for `FacadeIdCandidates`) in the app state (`500 Internal Server Error`).
+	MissingKey,
+
+	/// The path parameter is not a valid UUID string (`400 Bad Request`).
+	Parse(UuidParseError),
+
+	/// The UUID is not a `UUIDv4` facade (`404 Not Found`).
+	NotFacade(UuidValidationError),
+}
+
+impl FacadeIdRejection {
+	/// Get the HTTP status code for this rejection.
+	#[must_use]
+	pub fn status_code(&self) -> u16 {
+		match self {
+			FacadeIdRejection::MissingPathParam | FacadeIdRejection::MissingKey => 500,
+			FacadeIdRejection::MissingQueryParam(_) | FacadeIdRejection::Parse(_) => 400,
+			FacadeIdRejection::NotFacade(_) => 404,
+		}
+	}
+}
+
+impl std::fmt::Display for FacadeIdRejection {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			FacadeIdRejection::MissingPathParam => {
+				write!(f, "Route must have exactly one path parameter")
+			}
+			FacadeIdRejection::MissingQueryParam(name) => {
+				write!(f, "Missing query parameter {name}")
+			}
+			FacadeIdRejection::MissingKey => write!(f, "No key in the app state"),
+			FacadeIdRejection::Parse(err) => write!(f, "{err}"),
+			FacadeIdRejection::NotFacade(_) => write!(f, "Not found"),
+		}
+	}
+}
+
+impl std::error::Error for FacadeIdRejection {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			FacadeIdRejection::Parse(err) => Some(err),
+			FacadeIdRejection::NotFacade(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+#[cfg(feature = "axum")]
+mod axum_impl {
+	use axum::extract::{FromRef, FromRequestParts, Path};
+	use axum::http::request::Parts;
+	use axum::http::StatusCode;
+	use axum::response::{IntoResponse, Response};
+
+	use super::{FacadeId, FacadeIdRejection, FacadeKey, FacadeQuery, FacadeQueryParam};
+
+	impl<S> FromRequestParts<S> for FacadeId
+	where
+		S: Send + Sync,
+		FacadeKey: FromRef<S>,
+	{
+		type Rejection = FacadeIdRejection;
+
+		async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+			let Path(facade) = Path::<String>::from_request_parts(parts, state)
+				.await
+				.map_err(|_| FacadeIdRejection::MissingPathParam)?;
+
+			FacadeId::decode(&facade, FacadeKey::from_ref(state).key())
+		}
+	}
+
+	impl<S, P> FromRequestParts<S> for FacadeQuery<P>
+	where
+		S: Send + Sync,
+		FacadeKey: FromRef<S>,
+		P: FacadeQueryParam,
+	{
+		type Rejection = FacadeIdRejection;
+
+		async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+			let query = parts.uri.query().unwrap_or_default();
+			FacadeQuery::decode(query, FacadeKey::from_ref(state).key())
+		}
+	}
+
+	#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+	impl<S> FromRequestParts<S> for super::FacadeIdCandidates
+	where
+		S: Send + Sync,
+		std::sync::Arc<crate::keyring::Keyring>: FromRef<S>,
+	{
+		type Rejection = FacadeIdRejection;
+
+		async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+			let Path(facade) = Path::<String>::from_request_parts(parts, state)
+				.await
+				.map_err(|_| FacadeIdRejection::MissingPathParam)?;
+
+			let keyring = std::sync::Arc::<crate::keyring::Keyring>::from_ref(state);
+			Self::decode(&facade, &keyring, std::time::SystemTime::now())
+		}
+	}
+
+	impl IntoResponse for FacadeIdRejection {
+		fn into_response(self) -> Response {
+			let status = StatusCode::from_u16(self.status_code())
+				.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+			(status, self.to_string()).into_response()
+		}
+	}
+}
+
+#[cfg(feature = "actix-web")]
+mod actix_impl {
+	use std::future::{ready, Ready};
+
+	use actix_web::dev::Payload;
+	use actix_web::http::StatusCode;
+	use actix_web::{web, FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+	use super::{FacadeId, FacadeIdRejection, FacadeKey, FacadeQuery, FacadeQueryParam};
+	use crate::key::UuidV47Key;
+
+	impl FromRequest for FacadeId {
+		type Error = FacadeIdRejection;
+		type Future = Ready<Result<Self, Self::Error>>;
+
+		fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+			ready(extract(req))
+		}
+	}
+
+	impl<P: FacadeQueryParam> FromRequest for FacadeQuery<P> {
+		type Error = FacadeIdRejection;
+		type Future = Ready<Result<Self, Self::Error>>;
+
+		fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+			ready(key(req).and_then(|key| FacadeQuery::decode(req.query_string(), &key)))
+		}
+	}
+
+	#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+	impl FromRequest for super::FacadeIdCandidates {
+		type Error = FacadeIdRejection;
+		type Future = Ready<Result<Self, Self::Error>>;
+
+		fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+			let keyring = req
+				.app_data::<web::Data<crate::keyring::Keyring>>()
+				.ok_or(FacadeIdRejection::MissingKey);
+
+			ready(keyring.and_then(|keyring| {
+				let facade = path_param(req)?;
+				Self::decode(facade, keyring, std::time::SystemTime::now())
+			}))
+		}
+	}
+
+	fn key(req: &HttpRequest) -> Result<UuidV47Key, FacadeIdRejection> {
+		req.app_data::<web::Data<FacadeKey>>()
+			.map(|key| *key.key())
+			.or_else(|| req.app_data::<FacadeKey>().map(|key| *key.key()))
+			.ok_or(FacadeIdRejection::MissingKey)
+	}
+
+	fn extract(req: &HttpRequest) -> Result<FacadeId, FacadeIdRejection> {
+		let key = key(req)?;
+		FacadeId::decode(path_param(req)?, &key)
+	}
+
+	/// The single path parameter of the route.
+	fn path_param(req: &HttpRequest) -> Result<&str, FacadeIdRejection> {
+		let params = req.match_info();
+		if params.segment_count() == 0 || params.iter().count() != 1 {
+			return Err(FacadeIdRejection::MissingPathParam);
+		}
+		params
+			.iter()
+			.next()
+			.map(|(_, facade)| facade)
+			.ok_or(FacadeIdRejection::MissingPathParam)
+	}
+
+	impl ResponseError for FacadeIdRejection {
+		fn status_code(&self) -> StatusCode {
+			StatusCode::from_u16(FacadeIdRejection::status_code(self))
+				.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+		}
+
+		fn error_response(&self) -> HttpResponse {
+			HttpResponse::build(ResponseError::status_code(self)).body(self.to_string())
+		}
+	}
+}
@@ -0,0 +1,90 @@
+use crate::key::UuidV47Key;
+use crate::siphash::siphash24;
+
+/// Number of Feistel rounds.
+const ROUNDS: u8 = 8;
+
+/// Mask for one 61-bit half of the 122-bit payload.
+const HALF_MASK: u64 = (1 << 61) - 1;
+
+/// Domain separation prefix of the round function input ("u47f" + scheme version 1).
+const DOMAIN: [u8; 5] = [b'u', b'4', b'7', b'f', 1];
+
+/// Round function: SipHash-2-4 over `[DOMAIN][round][0, 0][half (little-endian u64)]`.
+///
+/// The 16-byte input never collides with the 10-byte input of the reference scheme.
+#[inline(always)]
+fn round_function(key: &UuidV47Key, round: u8, half: u64) -> u64 {
+	let mut msg = [0u8; 16];
+	msg[0..5].copy_from_slice(&DOMAIN);
+	msg[5] = round;
+	msg[8..16].copy_from_slice(&half.to_le_bytes());
+
+	siphash24(&msg, key.k0, key.k1) & HALF_MASK
+}
+
+/// Split a 122-bit payload into two 61-bit halves.
+#[inline(always)]
+#[allow(clippy::cast_possible_truncation)]
+fn split(payload: u128) -> (u64, u64) {
+	(
+		(payload >> 61) as u64 & HALF_MASK,
+		payload as u64 & HALF_MASK,
+	)
+}
+
+/// Join two 61-bit halves into a 122-bit payload.
+#[inline(always)]
+fn join(left: u64, right: u64) -> u128 {
+	(u128::from(left) << 61) | u128::from(right)
+}
+
+/// Keyed permutation over 122-bit payloads (balanced Feistel network).
+#[inline]
+pub(crate) fn permute(payload: u128, key: &UuidV47Key) -> u128 {
+	let (mut left, mut right) = split(payload);
+	for round in 0..ROUNDS {
+		let next = left ^ round_function(key, round, right);
+		left = right;
+		right = next;
+	}
+
+	join(left, right)
+}
+
+/// Inverse of [`permute`].
+#[inline]
+pub(crate) fn unpermute(payload: u128, key: &UuidV47Key) -> u128 {
+	let (mut left, mut right) = split(payload);
+	for round in (0..ROUNDS).rev() {
+		let prev = right ^ round_function(key, round, left);
+		right = left;
+		left = prev;
+	}
+
+	join(left, right)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_permute_roundtrip() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		let full = (1u128 << 122) - 1;
+
+		for payload in [
+			0,
+			1,
+			full,
+			full >> 61,
+			0x0123_4567_89AB_CDEF_0123_4567_89AB_CDEF & full,
+		] {
+			let permuted = permute(payload, &key);
+			assert!(permuted <= full);
+			assert_ne!(permuted, payload);
+			assert_eq!(unpermute(permuted, &key), payload);
+		}
+	}
+}
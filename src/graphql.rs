@@ -0,0 +1,133 @@
+//! GraphQL scalars (async-graphql).
+//!
+//! Both types are exposed as the built-in `ID` scalar, so the schema does not change.<br>
+//! The key is taken from the request data as a [`FacadeKey`] (`Request::data` or `Schema::build(..).data`),
+//! which refuses weak keys unless created with [`FacadeKey::new_allow_weak_key`].
+//!
+//! ```ignore
+//! #[Object]
+//! impl Query {
+//!     async fn item(&self, ctx: &Context<'_>, id: FacadeInput) -> Result<Item> {
+//!         let v7 = id.decode(ctx)?;
+//!         // ...
+//!     }
+//! }
+//!
+//! #[SimpleObject]
+//! struct Item {
+//!     id: FacadeOutput,
+//! }
+//! ```
+
+use std::borrow::Cow;
+
+use async_graphql::parser::types::Field;
+use async_graphql::registry::Registry;
+use async_graphql::{
+	Context, ContextSelectionSet, InputType, InputValueError, InputValueResult, OutputType,
+	Positioned, ServerError, ServerResult, Value, ID,
+};
+
+use crate::error::UuidValidationError;
+use crate::key::FacadeKey;
+use crate::sentinel::SentinelPolicy;
+use crate::uuid::Uuid128;
+
+/// Internal `UUIDv7` serialized as a `UUIDv4` facade `ID`.
+///
+/// Nil and Max are passed through unchanged, any other non `UUIDv7` is a field error.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct FacadeOutput(pub Uuid128);
+
+impl From<Uuid128> for FacadeOutput {
+	fn from(uuid: Uuid128) -> Self {
+		Self(uuid)
+	}
+}
+
+impl OutputType for FacadeOutput {
+	fn type_name() -> Cow<'static, str> {
+		<ID as OutputType>::type_name()
+	}
+
+	fn create_type_info(registry: &mut Registry) -> String {
+		<ID as OutputType>::create_type_info(registry)
+	}
+
+	async fn resolve(
+		&self,
+		ctx: &ContextSelectionSet<'_>,
+		field: &Positioned<Field>,
+	) -> ServerResult<Value> {
+		let key = ctx
+			.data::<FacadeKey>()
+			.map_err(|err| err.into_server_error(field.pos))?;
+		let facade = self
+			.0
+			.checked_encode_as_v4facade(key.key(), SentinelPolicy::Passthrough)
+			.map_err(|err| ServerError::new(err.to_string(), Some(field.pos)))?;
+
+		Ok(Value::String(facade.to_string()))
+	}
+}
+
+/// `UUIDv4` facade `ID` argument.
+///
+/// Parsing validates the string and the version, parse failures are reported with the [`crate::UuidParseError`] message.<br>
+/// Input parsing has no access to the request data, decode in the resolver with [`FacadeInput::decode`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct FacadeInput(Uuid128);
+
+impl FacadeInput {
+	/// Get the `UUIDv4` facade as sent by the client.
+	#[must_use]
+	pub fn facade(&self) -> Uuid128 {
+		self.0
+	}
+
+	/// Decode into the internal `UUIDv7` with the [`FacadeKey`] of the request data.
+	///
+	/// # Errors
+	///
+	/// Returns an error if no `FacadeKey` is in the request data.
+	pub fn decode(&self, ctx: &Context<'_>) -> async_graphql::Result<Uuid128> {
+		Ok(self.0.decode_from_v4facade(ctx.data::<FacadeKey>()?.key()))
+	}
+}
+
+impl InputType for FacadeInput {
+	type RawValueType = Uuid128;
+
+	fn type_name() -> Cow<'static, str> {
+		<ID as InputType>::type_name()
+	}
+
+	fn create_type_info(registry: &mut Registry) -> String {
+		<ID as InputType>::create_type_info(registry)
+	}
+
+	fn parse(value: Option<Value>) -> InputValueResult<Self> {
+		let value = value.unwrap_or_default();
+		let Value::String(s) = &value else {
+			return Err(InputValueError::expected_type(value));
+		};
+
+		let facade: Uuid128 = s.parse().map_err(InputValueError::custom)?;
+		if facade.is_nil() || facade.is_max() {
+			return Err(InputValueError::custom(UuidValidationError::Sentinel));
+		}
+		if facade.uuid_version() != 4 {
+			return Err(InputValueError::custom(UuidValidationError::InvalidVersion));
+		}
+
+		Ok(Self(facade))
+	}
+
+	fn to_value(&self) -> Value {
+		Value::String(self.0.to_string())
+	}
+
+	fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+		Some(&self.0)
+	}
+}
@@ -0,0 +1,497 @@
+//! Key derivation from passphrases with a memory-hard KDF.
+
+use crate::error::KdfError;
+use crate::key::UuidV47Key;
+
+/// Minimum salt length in bytes.
+pub const KDF_MIN_SALT_LEN: usize = 8;
+
+/// Maximum memory of the parameters, in bytes.
+const MAX_MEMORY: u64 = 4 << 30;
+
+/// Maximum Argon2id passes.
+const MAX_T_COST: u32 = 16;
+
+/// Maximum scrypt `ln`, so the memory (`128 * r * 2^ln` bytes) cannot overflow.
+const MAX_SCRYPT_LOG_N: u8 = 24;
+
+/// Maximum scrypt block size.
+const MAX_SCRYPT_R: u32 = 32;
+
+/// Maximum parallelism of either algorithm.
+const MAX_PARALLELISM: u32 = 16;
+
+/// Minimum Argon2id memory in KiB (the OWASP recommendation).
+const MIN_ARGON2_M_COST: u32 = 19 * 1024;
+
+/// Minimum scrypt `ln`.
+const MIN_SCRYPT_LOG_N: u8 = 15;
+
+/// Minimum scrypt block size.
+const MIN_SCRYPT_R: u32 = 8;
+
+/// Parameters of [`UuidV47Key::from_passphrase`].
+///
+/// The string form follows the PHC string format without salt and hash, so it can be stored next to the salt
+/// and the same key re-derived by every service:
+///
+/// | Algorithm | String | Feature |
+/// | --- | --- | --- |
+/// | Argon2id (v1.3) | `$argon2id$v=19$m=19456,t=2,p=1` | `kdf-argon2` |
+/// | scrypt | `$scrypt$ln=17,r=8,p=1` | `kdf-scrypt` |
+///
+/// Only the canonical form is accepted, so equal parameters always have the same string.
+///
+/// [`UuidV47Key::from_passphrase`] refuses parameters below the minimum cost
+/// (Argon2id `m` of 19456 KiB, scrypt `ln` of 15 and `r` of 8) and above the ceiling
+/// (4 GiB of memory, 16 passes, `r` of 32, parallelism of 16), whatever the string says.
+///
+/// # Examples
+/// ```
+/// use uuid47::KdfParams;
+///
+/// let params: KdfParams = "$argon2id$v=19$m=19456,t=2,p=1".parse().unwrap();
+/// assert_eq!(params, KdfParams::argon2id_default());
+/// assert_eq!(params.to_string(), "$argon2id$v=19$m=19456,t=2,p=1");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum KdfParams {
+	/// Argon2id, version 1.3.
+	Argon2id {
+		/// Memory in KiB.
+		m_cost: u32,
+		/// Number of passes.
+		t_cost: u32,
+		/// Degree of parallelism (lanes).
+		p_cost: u32,
+	},
+
+	/// scrypt.
+	Scrypt {
+		/// Log2 of the CPU/memory cost `N`.
+		log_n: u8,
+		/// Block size.
+		r: u32,
+		/// Parallelization.
+		p: u32,
+	},
+}
+
+impl KdfParams {
+	/// Argon2id with 19 MiB, 2 passes and 1 lane (the OWASP recommendation).
+	#[must_use]
+	pub fn argon2id_default() -> Self {
+		KdfParams::Argon2id {
+			m_cost: 19 * 1024,
+			t_cost: 2,
+			p_cost: 1,
+		}
+	}
+
+	/// scrypt with `N = 2^17`, `r = 8` and `p = 1` (the OWASP recommendation).
+	#[must_use]
+	pub fn scrypt_default() -> Self {
+		KdfParams::Scrypt {
+			log_n: 17,
+			r: 8,
+			p: 1,
+		}
+	}
+}
+
+impl KdfParams {
+	/// Check the ceiling, then the minimum cost unless `allow_weak_params`.
+	fn check(&self, allow_weak_params: bool) -> Result<(), KdfError> {
+		let (memory, weak) = match *self {
+			KdfParams::Argon2id {
+				m_cost,
+				t_cost,
+				p_cost,
+			} => {
+				if t_cost > MAX_T_COST || p_cost > MAX_PARALLELISM {
+					return Err(KdfError::InvalidParams);
+				}
+				(u64::from(m_cost) * 1024, m_cost < MIN_ARGON2_M_COST)
+			}
+			KdfParams::Scrypt { log_n, r, p } => {
+				if log_n > MAX_SCRYPT_LOG_N || r > MAX_SCRYPT_R || p > MAX_PARALLELISM {
+					return Err(KdfError::InvalidParams);
+				}
+				(
+					(128 * u64::from(r)) << log_n,
+					log_n < MIN_SCRYPT_LOG_N || r < MIN_SCRYPT_R,
+				)
+			}
+		};
+
+		if memory > MAX_MEMORY {
+			return Err(KdfError::InvalidParams);
+		}
+		if weak && !allow_weak_params {
+			return Err(KdfError::WeakParams);
+		}
+
+		Ok(())
+	}
+}
+
+impl std::fmt::Display for KdfParams {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KdfParams::Argon2id {
+				m_cost,
+				t_cost,
+				p_cost,
+			} => write!(f, "$argon2id$v=19$m={m_cost},t={t_cost},p={p_cost}"),
+			KdfParams::Scrypt { log_n, r, p } => write!(f, "$scrypt$ln={log_n},r={r},p={p}"),
+		}
+	}
+}
+
+impl std::str::FromStr for KdfParams {
+	type Err = KdfError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut fields = s.split('$');
+		if fields.next() != Some("") {
+			return Err(KdfError::InvalidParams);
+		}
+
+		let params = match (fields.next(), fields.next(), fields.next(), fields.next()) {
+			(Some("argon2id"), Some("v=19"), Some(values), None) => {
+				let [m_cost, t_cost, p_cost] = parse_values(values, ["m", "t", "p"])?;
+				KdfParams::Argon2id {
+					m_cost,
+					t_cost,
+					p_cost,
+				}
+			}
+			(Some("scrypt"), Some(values), None, None) => {
+				let [log_n, r, p] = parse_values(values, ["ln", "r", "p"])?;
+				KdfParams::Scrypt {
+					log_n: log_n.try_into().map_err(|_| KdfError::InvalidParams)?,
+					r,
+					p,
+				}
+			}
+			(None | Some("" | "argon2id" | "scrypt"), ..) => return Err(KdfError::InvalidParams),
+			_ => return Err(KdfError::UnsupportedAlgorithm),
+		};
+
+		Ok(params)
+	}
+}
+
+/// Parse `name=value` pairs in the given order, with canonical decimal values.
+fn parse_values<const N: usize>(s: &str, names: [&str; N]) -> Result<[u32; N], KdfError> {
+	let mut pairs = s.split(',');
+	let mut values = [0; N];
+
+	for (value, name) in values.iter_mut().zip(names) {
+		let digits = pairs
+			.next()
+			.and_then(|pair| pair.strip_prefix(name))
+			.and_then(|pair| pair.strip_prefix('='))
+			.ok_or(KdfError::InvalidParams)?;
+
+		let canonical = !digits.is_empty()
+			&& digits.bytes().all(|b| b.is_ascii_digit())
+			&& (digits == "0" || !digits.starts_with('0'));
+		if !canonical {
+			return Err(KdfError::InvalidParams);
+		}
+
+		*value = digits.parse().map_err(|_| KdfError::InvalidParams)?;
+	}
+
+	if pairs.next().is_some() {
+		return Err(KdfError::InvalidParams);
+	}
+
+	Ok(values)
+}
+
+impl UuidV47Key {
+	/// Derives a key from a passphrase with a memory-hard KDF.
+	///
+	/// The same passphrase, salt and parameters always give the same key.
+	/// Store the salt (at least [`KDF_MIN_SALT_LEN`] bytes, 16 random bytes recommended)
+	/// and the [`KdfParams`] string alongside the service configuration, never the passphrase.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the salt is too short, the parameters are below the minimum cost or out of range,
+	/// the feature of the algorithm is disabled, or the derived key is rejected by [`UuidV47Key::validate`].
+	///
+	/// # Examples
+	/// ```
+	/// # #[cfg(feature = "kdf-argon2")]
+	/// # {
+	/// use uuid47::{KdfParams, UuidV47Key};
+	///
+	/// let params: KdfParams = "$argon2id$v=19$m=19456,t=2,p=1".parse().unwrap();
+	/// let key = UuidV47Key::from_passphrase("correct horse battery staple", "uuid47-salt-0001", &params).unwrap();
+	///
+	/// assert_eq!(key, UuidV47Key::new(0x32c55621cb7688aa, 0x5e30bab62baf2ea7));
+	/// # }
+	/// ```
+	pub fn from_passphrase(
+		passphrase: impl AsRef<[u8]>,
+		salt: impl AsRef<[u8]>,
+		params: &KdfParams,
+	) -> Result<Self, KdfError> {
+		Self::derive(passphrase.as_ref(), salt.as_ref(), params, false)
+	}
+
+	/// Same as [`UuidV47Key::from_passphrase`], but accepts parameters below the minimum cost.
+	///
+	/// Only meant for tests and cross-checking vectors, the ceiling still applies.
+	///
+	/// # Errors
+	///
+	/// See [`UuidV47Key::from_passphrase`].
+	pub fn from_passphrase_allow_weak_params(
+		passphrase: impl AsRef<[u8]>,
+		salt: impl AsRef<[u8]>,
+		params: &KdfParams,
+	) -> Result<Self, KdfError> {
+		Self::derive(passphrase.as_ref(), salt.as_ref(), params, true)
+	}
+
+	fn derive(
+		passphrase: &[u8],
+		salt: &[u8],
+		params: &KdfParams,
+		allow_weak_params: bool,
+	) -> Result<Self, KdfError> {
+		if salt.len() < KDF_MIN_SALT_LEN {
+			return Err(KdfError::SaltTooShort);
+		}
+		params.check(allow_weak_params)?;
+
+		let mut out = [0u8; 16];
+		match *params {
+			#[cfg(feature = "kdf-argon2")]
+			KdfParams::Argon2id {
+				m_cost,
+				t_cost,
+				p_cost,
+			} => {
+				let params = argon2::Params::new(m_cost, t_cost, p_cost, Some(out.len()))
+					.map_err(|_| KdfError::InvalidParams)?;
+				argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+					.hash_password_into(passphrase, salt, &mut out)
+					.map_err(|_| KdfError::InvalidParams)?;
+			}
+			#[cfg(feature = "kdf-scrypt")]
+			KdfParams::Scrypt { log_n, r, p } => {
+				let params = scrypt::Params::new(log_n, r, p, out.len())
+					.map_err(|_| KdfError::InvalidParams)?;
+				scrypt::scrypt(passphrase, salt, &params, &mut out)
+					.map_err(|_| KdfError::InvalidParams)?;
+			}
+			#[allow(unreachable_patterns)]
+			_ => return Err(KdfError::UnsupportedAlgorithm),
+		}
+
+		let key = Self::from_bytes(out);
+		key.validate().map_err(KdfError::WeakKey)?;
+
+		Ok(key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_params_roundtrip() {
+		for s in [
+			"$argon2id$v=19$m=19456,t=2,p=1",
+			"$argon2id$v=19$m=0,t=0,p=0",
+			"$scrypt$ln=17,r=8,p=1",
+			"$scrypt$ln=255,r=4294967295,p=1",
+		] {
+			let params: KdfParams = s.parse().unwrap();
+			assert_eq!(params.to_string(), s);
+		}
+
+		assert_eq!(
+			KdfParams::scrypt_default().to_string(),
+			"$scrypt$ln=17,r=8,p=1"
+		);
+	}
+
+	#[test]
+	fn test_params_invalid() {
+		for s in [
+			"",
+			"$",
+			"argon2id$v=19$m=19456,t=2,p=1",
+			"$argon2id$m=19456,t=2,p=1",
+			"$argon2id$v=16$m=19456,t=2,p=1",
+			"$argon2id$v=19$t=2,m=19456,p=1",
+			"$argon2id$v=19$m=19456,t=2",
+			"$argon2id$v=19$m=19456,t=2,p=1,x=0",
+			"$argon2id$v=19$m=019456,t=2,p=1",
+			"$argon2id$v=19$m=+19456,t=2,p=1",
+			"$argon2id$v=19$m=19456,t=2,p=1$",
+			"$argon2id$v=19$m=4294967296,t=2,p=1",
+			"$scrypt$ln=256,r=8,p=1",
+			"$scrypt$ln=17,r=8",
+			"$scrypt$ln=17, r=8,p=1",
+		] {
+			assert_eq!(s.parse::<KdfParams>(), Err(KdfError::InvalidParams), "{s}");
+		}
+
+		for s in ["$argon2i$v=19$m=19456,t=2,p=1", "$pbkdf2$i=1000"] {
+			assert_eq!(
+				s.parse::<KdfParams>(),
+				Err(KdfError::UnsupportedAlgorithm),
+				"{s}"
+			);
+		}
+	}
+
+	#[test]
+	fn test_params_limits() {
+		let derive = |s: &str, allow_weak_params| {
+			let params = s.parse().unwrap();
+			if allow_weak_params {
+				UuidV47Key::from_passphrase_allow_weak_params("passphrase", "saltsalt", &params)
+			} else {
+				UuidV47Key::from_passphrase("passphrase", "saltsalt", &params)
+			}
+		};
+
+		// below the minimum cost
+		for s in [
+			"$argon2id$v=19$m=0,t=0,p=0",
+			"$argon2id$v=19$m=19455,t=2,p=1",
+			"$scrypt$ln=14,r=8,p=1",
+			"$scrypt$ln=17,r=7,p=1",
+		] {
+			assert_eq!(derive(s, false), Err(KdfError::WeakParams), "{s}");
+		}
+
+		// above the ceiling, even with the opt-out
+		for s in [
+			"$argon2id$v=19$m=4194305,t=2,p=1",
+			"$argon2id$v=19$m=4294967295,t=2,p=1",
+			"$argon2id$v=19$m=19456,t=17,p=1",
+			"$argon2id$v=19$m=19456,t=2,p=17",
+			"$scrypt$ln=23,r=8,p=1",
+			"$scrypt$ln=40,r=8,p=1",
+			"$scrypt$ln=255,r=4294967295,p=1",
+			"$scrypt$ln=17,r=33,p=1",
+			"$scrypt$ln=17,r=8,p=17",
+		] {
+			assert_eq!(derive(s, false), Err(KdfError::InvalidParams), "{s}");
+			assert_eq!(derive(s, true), Err(KdfError::InvalidParams), "{s}");
+		}
+	}
+
+	#[test]
+	fn test_salt_too_short() {
+		let params = KdfParams::argon2id_default();
+
+		assert_eq!(
+			UuidV47Key::from_passphrase("passphrase", "salt", &params),
+			Err(KdfError::SaltTooShort)
+		);
+	}
+
+	/// Vectors from `openssl kdf -keylen 16 ... ARGON2ID`.
+	#[cfg(feature = "kdf-argon2")]
+	#[test]
+	fn test_argon2id_vectors() {
+		let vectors = [
+			(
+				"passphrase",
+				"saltsalt",
+				"$argon2id$v=19$m=64,t=1,p=1",
+				"4533aae3720087cc970434c6378579b3",
+			),
+			(
+				"passphrase",
+				"saltsalt",
+				"$argon2id$v=19$m=256,t=3,p=4",
+				"f1a57a2cbc255562d3c394f8f20c48a3",
+			),
+			(
+				"correct horse battery staple",
+				"uuid47-salt-0001",
+				"$argon2id$v=19$m=19456,t=2,p=1",
+				"32c55621cb7688aa5e30bab62baf2ea7",
+			),
+		];
+
+		for (passphrase, salt, params, expected) in vectors {
+			let key = UuidV47Key::from_passphrase_allow_weak_params(
+				passphrase,
+				salt,
+				&params.parse().unwrap(),
+			);
+			assert_eq!(key, Ok(expected.parse().unwrap()), "{params}");
+		}
+
+		let key = UuidV47Key::from_passphrase(
+			"correct horse battery staple",
+			"uuid47-salt-0001",
+			&KdfParams::argon2id_default(),
+		);
+		assert_eq!(key, Ok("32c55621cb7688aa5e30bab62baf2ea7".parse().unwrap()));
+
+		// m must be at least 8 KiB per lane
+		let params = "$argon2id$v=19$m=8,t=1,p=4".parse().unwrap();
+		assert_eq!(
+			UuidV47Key::from_passphrase_allow_weak_params("passphrase", "saltsalt", &params),
+			Err(KdfError::InvalidParams)
+		);
+	}
+
+	/// Vectors from Python `hashlib.scrypt(..., dklen=16)`.
+	#[cfg(feature = "kdf-scrypt")]
+	#[test]
+	fn test_scrypt_vectors() {
+		let vectors = [
+			(
+				"passphrase",
+				"saltsalt",
+				"$scrypt$ln=4,r=1,p=1",
+				"c46ffe6b550b927b663ba8d7cfdf6fda",
+			),
+			(
+				"passphrase",
+				"saltsalt",
+				"$scrypt$ln=10,r=8,p=2",
+				"51c1e41f457eb186b861d6c173324a77",
+			),
+		];
+
+		for (passphrase, salt, params, expected) in vectors {
+			let key = UuidV47Key::from_passphrase_allow_weak_params(
+				passphrase,
+				salt,
+				&params.parse().unwrap(),
+			);
+			assert_eq!(key, Ok(expected.parse().unwrap()), "{params}");
+		}
+
+		let params = "$scrypt$ln=10,r=8,p=0".parse().unwrap();
+		assert_eq!(
+			UuidV47Key::from_passphrase_allow_weak_params("passphrase", "saltsalt", &params),
+			Err(KdfError::InvalidParams)
+		);
+	}
+
+	#[cfg(not(feature = "kdf-scrypt"))]
+	#[test]
+	fn test_unsupported_algorithm() {
+		assert_eq!(
+			UuidV47Key::from_passphrase("passphrase", "saltsalt", &KdfParams::scrypt_default()),
+			Err(KdfError::UnsupportedAlgorithm)
+		);
+	}
+}
@@ -1,5 +1,10 @@
+use crate::error::{KeyParseError, WeakKeyError};
+use crate::utils::hexval;
+
 /// Key structure for `UUIDv47` encoding/decoding.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+///
+/// `Debug` never prints the secret, so keys can sit in logged or panicking structures such as a `Keyring`.
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
 pub struct UuidV47Key {
 	/// First 64 bits of the key.
 	pub k0: u64,
@@ -8,10 +13,315 @@ pub struct UuidV47Key {
 	pub k1: u64,
 }
 
+/// The example key used throughout the documentation.
+const EXAMPLE_KEY: UuidV47Key = UuidV47Key {
+	k0: 0x0123_4567_89ab_cdef,
+	k1: 0xfedc_ba98_7654_3210,
+};
+
 impl UuidV47Key {
 	/// Creates a new key.
+	///
+	/// Any value is accepted, see [`UuidV47Key::new_checked`] to refuse weak keys.
 	#[must_use]
 	pub fn new(k0: u64, k1: u64) -> Self {
 		Self { k0, k1 }
 	}
+
+	/// Creates a new key, refusing weak keys.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the key fails [`UuidV47Key::validate`].
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{UuidV47Key, WeakKeyError};
+	///
+	/// assert_eq!(UuidV47Key::new_checked(0, 0), Err(WeakKeyError::Zero));
+	/// assert!(UuidV47Key::new_checked(0x5f1c9a0e7d3b2846, 0xb4e8126fa0c37d95).is_ok());
+	/// ```
+	pub fn new_checked(k0: u64, k1: u64) -> Result<Self, WeakKeyError> {
+		let key = Self::new(k0, k1);
+		key.validate()?;
+		Ok(key)
+	}
+
+	/// Generates a random key from the OS CSPRNG.
+	///
+	/// The result always passes [`UuidV47Key::validate`].
+	///
+	/// # Errors
+	///
+	/// Returns an error if the OS random source is unavailable.
+	#[cfg(feature = "getrandom")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+	pub fn generate() -> Result<Self, getrandom::Error> {
+		loop {
+			let mut bytes = [0u8; 16];
+			getrandom::fill(&mut bytes)?;
+
+			let key = Self::from_bytes(bytes);
+			if key.validate().is_ok() {
+				return Ok(key);
+			}
+		}
+	}
+
+	/// Checks the key is not obviously weak.
+	///
+	/// Rejects the all-zero key, the example key of the documentation, and low-entropy patterns:
+	/// a zero or repeated half, repeated or sequential bytes and nibbles, or a heavily unbalanced bit count.<br>
+	/// This is a heuristic against mistakes, not a proof of randomness. A random key fails with negligible probability.
+	///
+	/// # Errors
+	///
+	/// Returns the reason the key was rejected.
+	pub fn validate(&self) -> Result<(), WeakKeyError> {
+		if self.k0 == 0 && self.k1 == 0 {
+			return Err(WeakKeyError::Zero);
+		}
+		if *self == EXAMPLE_KEY {
+			return Err(WeakKeyError::ExampleKey);
+		}
+
+		let value = self.to_u128();
+		let be = self.to_bytes();
+		let mut le = [0u8; 16];
+		le[0..8].copy_from_slice(&self.k0.to_le_bytes());
+		le[8..16].copy_from_slice(&self.k1.to_le_bytes());
+
+		let weak = self.k0 == 0
+			|| self.k1 == 0
+			|| self.k0 == self.k1
+			|| [1, 2, 4].iter().any(|&period| is_periodic(&be, period))
+			|| is_progression(&be)
+			|| is_progression(&le)
+			|| is_progression(&nibbles(value))
+			|| !(32..=96).contains(&value.count_ones());
+
+		if weak {
+			Err(WeakKeyError::LowEntropy)
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Creates a key from 16 bytes (`k0` then `k1`, big-endian).
+	#[must_use]
+	pub fn from_bytes(bytes: [u8; 16]) -> Self {
+		let value = u128::from_be_bytes(bytes);
+		#[allow(clippy::cast_possible_truncation)]
+		Self::new((value >> 64) as u64, value as u64)
+	}
+
+	/// Get the 16 bytes of the key (`k0` then `k1`, big-endian).
+	#[must_use]
+	pub fn to_bytes(&self) -> [u8; 16] {
+		self.to_u128().to_be_bytes()
+	}
+
+	fn to_u128(self) -> u128 {
+		(u128::from(self.k0) << 64) | u128::from(self.k1)
+	}
+}
+
+impl std::fmt::Debug for UuidV47Key {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("UuidV47Key(..)")
+	}
+}
+
+impl std::str::FromStr for UuidV47Key {
+	type Err = KeyParseError;
+
+	/// Parses 32 hex digits (`k0` then `k1`, big-endian), surrounding whitespace is ignored.
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::UuidV47Key;
+	///
+	/// let key: UuidV47Key = "5f1c9a0e7d3b2846b4e8126fa0c37d95\n".parse().unwrap();
+	/// assert_eq!(key, UuidV47Key::new(0x5f1c9a0e7d3b2846, 0xb4e8126fa0c37d95));
+	/// ```
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let hex = s.trim().as_bytes();
+		if hex.len() != 32 {
+			return Err(KeyParseError::InvalidLength);
+		}
+
+		let mut value = 0u128;
+		for &c in hex {
+			let nibble = hexval(c).ok_or(KeyParseError::InvalidCharacter)?;
+			value = (value << 4) | u128::from(nibble);
+		}
+
+		Ok(Self::from_bytes(value.to_be_bytes()))
+	}
+}
+
+/// Whether the bytes repeat with the given period.
+fn is_periodic(bytes: &[u8], period: usize) -> bool {
+	bytes.iter().skip(period).zip(bytes).all(|(a, b)| a == b)
+}
+
+/// Whether consecutive values have a constant (wrapping) difference, e.g. `00 01 02 ..`.
+fn is_progression(values: &[u8]) -> bool {
+	let step = values[1].wrapping_sub(values[0]);
+	values.windows(2).all(|w| w[1].wrapping_sub(w[0]) == step)
+}
+
+/// The 32 nibbles of the key, with the difference taken modulo 16 by shifting them to the high half.
+fn nibbles(value: u128) -> [u8; 32] {
+	#[allow(clippy::cast_possible_truncation)]
+	core::array::from_fn(|i| ((value >> (124 - 4 * i)) as u8) << 4)
+}
+
+/// Key of the integrations reading it from a request, checked with [`UuidV47Key::validate`] when created.
+///
+/// Put it in the app state of the `FacadeId` and `FacadeQuery` extractors (`FacadeKey: FromRef<S>` for axum,
+/// `web::Data<FacadeKey>` for actix-web), or in the request data of the `graphql` scalars.
+///
+/// # Examples
+/// ```
+/// use uuid47::{FacadeKey, UuidV47Key, WeakKeyError};
+///
+/// let key = FacadeKey::new(UuidV47Key::new(0x5f1c9a0e7d3b2846, 0xb4e8126fa0c37d95)).unwrap();
+///
+/// let example = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+/// assert_eq!(FacadeKey::new(example), Err(WeakKeyError::ExampleKey));
+/// ```
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "async-graphql"))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FacadeKey(UuidV47Key);
+
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "async-graphql"))]
+impl FacadeKey {
+	/// Creates a key, refusing weak keys.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the key is weak, see [`UuidV47Key::validate`].
+	pub fn new(key: UuidV47Key) -> Result<Self, WeakKeyError> {
+		key.validate()?;
+		Ok(Self(key))
+	}
+
+	/// Same as [`FacadeKey::new`], but accepts weak keys.
+	#[must_use]
+	pub fn new_allow_weak_key(key: UuidV47Key) -> Self {
+		Self(key)
+	}
+
+	/// Get the key.
+	#[must_use]
+	pub fn key(&self) -> &UuidV47Key {
+		&self.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_validate() {
+		assert_eq!(UuidV47Key::new(0, 0).validate(), Err(WeakKeyError::Zero));
+		assert_eq!(EXAMPLE_KEY.validate(), Err(WeakKeyError::ExampleKey));
+
+		let weak = [
+			(0, 0x5f1c_9a0e_7d3b_2846),
+			(0x5f1c_9a0e_7d3b_2846, 0),
+			(0x5f1c_9a0e_7d3b_2846, 0x5f1c_9a0e_7d3b_2846),
+			(u64::MAX, u64::MAX),
+			(0x4242_4242_4242_4242, 0x4242_4242_4242_4242),
+			(0xdead_beef_dead_beef, 0xdead_beef_dead_beef),
+			(0x0001_0203_0405_0607, 0x0809_0a0b_0c0d_0e0f),
+			(0x0706_0504_0302_0100, 0x0f0e_0d0c_0b0a_0908),
+			(0x0123_4567_89ab_cdef, 0x0123_4567_89ab_cdef),
+			(0xfedc_ba98_7654_3210, 0xfedc_ba98_7654_3210),
+			(0x0000_0000_0000_0001, 0x0000_0000_0000_0002),
+			(0x0000_0001_0000_0000, 0x8000_0000_0000_0100),
+		];
+		for (k0, k1) in weak {
+			assert_eq!(
+				UuidV47Key::new(k0, k1).validate(),
+				Err(WeakKeyError::LowEntropy),
+				"{k0:016x}{k1:016x}"
+			);
+		}
+
+		assert_eq!(
+			UuidV47Key::new(0x5f1c_9a0e_7d3b_2846, 0xb4e8_126f_a0c3_7d95).validate(),
+			Ok(())
+		);
+	}
+
+	#[test]
+	fn test_from_str() {
+		let key: UuidV47Key = " 5F1C9A0E7D3B2846b4e8126fa0c37d95 ".parse().unwrap();
+		assert_eq!(
+			key,
+			UuidV47Key::new(0x5f1c_9a0e_7d3b_2846, 0xb4e8_126f_a0c3_7d95)
+		);
+
+		assert_eq!(
+			"5f1c9a0e7d3b2846b4e8126fa0c37d9".parse::<UuidV47Key>(),
+			Err(KeyParseError::InvalidLength)
+		);
+		assert_eq!(
+			"5f1c9a0e7d3b2846b4e8126fa0c37d9g".parse::<UuidV47Key>(),
+			Err(KeyParseError::InvalidCharacter)
+		);
+		assert_eq!(
+			"0x5f1c9a0e7d3b2846b4e8126fa0c37d".parse::<UuidV47Key>(),
+			Err(KeyParseError::InvalidCharacter)
+		);
+	}
+
+	#[test]
+	fn test_debug_redacted() {
+		let key = UuidV47Key::new(0x5f1c_9a0e_7d3b_2846, 0xb4e8_126f_a0c3_7d95);
+		assert_eq!(format!("{key:?}"), "UuidV47Key(..)");
+		assert_eq!(format!("{key:#?}"), "UuidV47Key(..)");
+	}
+
+	#[test]
+	fn test_bytes_roundtrip() {
+		let key = UuidV47Key::from_bytes(core::array::from_fn(|i| i as u8));
+
+		assert_eq!(
+			key,
+			UuidV47Key::new(0x0001_0203_0405_0607, 0x0809_0a0b_0c0d_0e0f)
+		);
+		assert_eq!(UuidV47Key::from_bytes(key.to_bytes()), key);
+	}
+
+	#[test]
+	fn test_validate_random_keys() {
+		// keys from a deterministic PRNG should pass
+		let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+		let mut next = || {
+			state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+			let mut z = state;
+			z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+			z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+			z ^ (z >> 31)
+		};
+
+		for _ in 0..10_000 {
+			let key = UuidV47Key::new(next(), next());
+			assert_eq!(key.validate(), Ok(()), "{:016x}{:016x}", key.k0, key.k1);
+		}
+	}
+
+	#[cfg(feature = "getrandom")]
+	#[test]
+	fn test_generate() {
+		let a = UuidV47Key::generate().unwrap();
+		let b = UuidV47Key::generate().unwrap();
+
+		assert_eq!(a.validate(), Ok(()));
+		assert_ne!(a, b);
+	}
 }
@@ -0,0 +1,417 @@
+//! Keyring files with key IDs, activation dates and a primary key.
+
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+use crate::error::KeyringError;
+use crate::key::UuidV47Key;
+
+/// A set of keys for rotation, loaded from a JSON or TOML file.
+///
+/// The primary key encodes new facades, every key that is active can decode.<br>
+/// `activates_at` is optional, as `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ` (UTC).
+/// The primary key must already be active when the keyring is loaded.
+///
+/// ```json
+/// {
+///   "keys": [
+///     { "id": "2024-01", "key": "5f1c9a0e7d3b2846b4e8126fa0c37d95" },
+///     { "id": "2025-01", "key": "9d2e71c04ab8f356e1075bd93c6a28f4", "activates_at": "2025-01-01", "primary": true }
+///   ]
+/// }
+/// ```
+///
+/// ```toml
+/// [[keys]]
+/// id = "2024-01"
+/// key = "5f1c9a0e7d3b2846b4e8126fa0c37d95"
+///
+/// [[keys]]
+/// id = "2025-01"
+/// key = "9d2e71c04ab8f356e1075bd93c6a28f4"
+/// activates_at = "2025-01-01"
+/// primary = true
+/// ```
+///
+/// Load a file with [`KeyLoader::load_keyring`](crate::KeyLoader::load_keyring).
+#[derive(Debug, Clone)]
+pub struct Keyring {
+	entries: Vec<KeyringEntry>,
+	primary: usize,
+}
+
+/// A key of a [`Keyring`].
+#[derive(Debug, Clone)]
+pub struct KeyringEntry {
+	/// The key ID.
+	pub id: String,
+
+	/// The key.
+	pub key: UuidV47Key,
+
+	/// The time from which the key is in use, `None` if always.
+	pub activates_at: Option<SystemTime>,
+
+	/// Whether this is the key encoding new facades.
+	pub primary: bool,
+}
+
+impl KeyringEntry {
+	/// Whether the key is in use at the given time.
+	#[must_use]
+	pub fn is_active_at(&self, time: SystemTime) -> bool {
+		self.activates_at
+			.is_none_or(|activates_at| activates_at <= time)
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawKeyring {
+	keys: Vec<RawEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEntry {
+	id: String,
+	key: String,
+	#[serde(default)]
+	activates_at: Option<String>,
+	#[serde(default)]
+	primary: bool,
+}
+
+impl Keyring {
+	/// Parses a JSON keyring, refusing weak keys.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the keyring is invalid.
+	#[cfg(feature = "keyring-json")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "keyring-json")))]
+	pub fn from_json(s: &str) -> Result<Self, KeyringError> {
+		Self::parse(s, Some("json"), false)
+	}
+
+	/// Parses a TOML keyring, refusing weak keys.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the keyring is invalid.
+	#[cfg(feature = "keyring-toml")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "keyring-toml")))]
+	pub fn from_toml(s: &str) -> Result<Self, KeyringError> {
+		Self::parse(s, Some("toml"), false)
+	}
+
+	/// Parses a keyring in the format given by the file extension.
+	pub(crate) fn parse(
+		s: &str,
+		extension: Option<&str>,
+		allow_weak_keys: bool,
+	) -> Result<Self, KeyringError> {
+		// parser messages may quote the input, only report the line
+		let raw: RawKeyring = match extension {
+			#[cfg(feature = "keyring-json")]
+			Some("json") => serde_json::from_str(s).map_err(|err| KeyringError::Syntax(err.line()))?,
+			#[cfg(feature = "keyring-toml")]
+			Some("toml") => toml::from_str(s).map_err(|err| {
+				let offset = err.span().map_or(0, |span| span.start);
+				KeyringError::Syntax(line_of(s, offset))
+			})?,
+			_ => return Err(KeyringError::UnknownFormat),
+		};
+
+		Self::from_raw(raw, allow_weak_keys, SystemTime::now())
+	}
+
+	fn from_raw(
+		raw: RawKeyring,
+		allow_weak_keys: bool,
+		now: SystemTime,
+	) -> Result<Self, KeyringError> {
+		let mut entries: Vec<KeyringEntry> = Vec::with_capacity(raw.keys.len());
+		for entry in raw.keys {
+			if entries.iter().any(|other| other.id == entry.id) {
+				return Err(KeyringError::DuplicateId(entry.id));
+			}
+
+			let key: UuidV47Key = match entry.key.parse() {
+				Ok(key) => key,
+				Err(err) => return Err(KeyringError::InvalidKey(entry.id, err)),
+			};
+			if !allow_weak_keys {
+				if let Err(err) = key.validate() {
+					return Err(KeyringError::WeakKey(entry.id, err));
+				}
+			}
+
+			let activates_at = match entry.activates_at.as_deref().map(parse_date) {
+				None => None,
+				Some(Some(time)) => Some(time),
+				Some(None) => return Err(KeyringError::InvalidDate(entry.id)),
+			};
+
+			entries.push(KeyringEntry {
+				id: entry.id,
+				key,
+				activates_at,
+				primary: entry.primary,
+			});
+		}
+
+		if entries.is_empty() {
+			return Err(KeyringError::Empty);
+		}
+
+		let mut primaries = entries
+			.iter()
+			.enumerate()
+			.filter(|(_, entry)| entry.primary);
+		let primary = primaries.next().ok_or(KeyringError::NoPrimary)?.0;
+		if primaries.next().is_some() {
+			return Err(KeyringError::MultiplePrimary);
+		}
+		if !entries[primary].is_active_at(now) {
+			return Err(KeyringError::PrimaryNotActive(entries[primary].id.clone()));
+		}
+
+		Ok(Self { entries, primary })
+	}
+
+	/// Get the primary entry.
+	#[must_use]
+	pub fn primary(&self) -> &KeyringEntry {
+		&self.entries[self.primary]
+	}
+
+	/// Get the key with the given ID.
+	#[must_use]
+	pub fn get(&self, id: &str) -> Option<&UuidV47Key> {
+		self.entries
+			.iter()
+			.find(|entry| entry.id == id)
+			.map(|entry| &entry.key)
+	}
+
+	/// Get every entry, in file order.
+	#[must_use]
+	pub fn entries(&self) -> &[KeyringEntry] {
+		&self.entries
+	}
+
+	/// Get the entries in use at the given time.
+	pub fn active_at(&self, time: SystemTime) -> impl Iterator<Item = &KeyringEntry> {
+		self.entries
+			.iter()
+			.filter(move |entry| entry.is_active_at(time))
+	}
+}
+
+/// Line (1-based) of the byte offset.
+#[cfg(feature = "keyring-toml")]
+fn line_of(s: &str, offset: usize) -> usize {
+	s.as_bytes()[..offset.min(s.len())]
+		.iter()
+		.filter(|&&b| b == b'\n')
+		.count()
+		+ 1
+}
+
+/// Parse `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SSZ` (UTC, from 1970).
+fn parse_date(s: &str) -> Option<SystemTime> {
+	fn number(s: &str) -> Option<u64> {
+		s.bytes()
+			.all(|b| b.is_ascii_digit())
+			.then(|| s.parse().ok())
+			.flatten()
+	}
+
+	let (date, time) = match s.split_once('T') {
+		Some((date, time)) => (date, Some(time.strip_suffix('Z')?)),
+		None => (s, None),
+	};
+
+	let mut parts = date.splitn(3, '-');
+	let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+	if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+		return None;
+	}
+	let (year, month, day) = (number(year)?, number(month)?, number(day)?);
+	if year < 1970 || !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+		return None;
+	}
+
+	let mut seconds = days_from_civil(year, month, day) * 86_400;
+	if let Some(time) = time {
+		let mut parts = time.splitn(3, ':');
+		let (hour, minute, second) = (parts.next()?, parts.next()?, parts.next()?);
+		if hour.len() != 2 || minute.len() != 2 || second.len() != 2 {
+			return None;
+		}
+		let (hour, minute, second) = (number(hour)?, number(minute)?, number(second)?);
+		if hour > 23 || minute > 59 || second > 59 {
+			return None;
+		}
+		seconds += hour * 3600 + minute * 60 + second;
+	}
+
+	SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+}
+
+fn days_in_month(year: u64, month: u64) -> u64 {
+	match month {
+		2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+			29
+		}
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// Days since 1970-01-01 of a valid date from 1970.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+	// shift the year to start in March so the leap day is last
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year / 400;
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+	era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::{KeyParseError, WeakKeyError};
+
+	fn raw(entries: &[(&str, &str, Option<&str>, bool)]) -> RawKeyring {
+		RawKeyring {
+			keys: entries
+				.iter()
+				.map(|&(id, key, activates_at, primary)| RawEntry {
+					id: id.into(),
+					key: key.into(),
+					activates_at: activates_at.map(Into::into),
+					primary,
+				})
+				.collect(),
+		}
+	}
+
+	const KEY_A: &str = "5f1c9a0e7d3b2846b4e8126fa0c37d95";
+	const KEY_B: &str = "9d2e71c04ab8f356e1075bd93c6a28f4";
+
+	#[test]
+	fn test_parse_date() {
+		let secs = |s| {
+			parse_date(s).map(|time| {
+				time.duration_since(SystemTime::UNIX_EPOCH)
+					.unwrap()
+					.as_secs()
+			})
+		};
+
+		assert_eq!(secs("1970-01-01"), Some(0));
+		assert_eq!(secs("2000-03-01"), Some(951_868_800));
+		assert_eq!(secs("2024-02-29T12:34:56Z"), Some(1_709_210_096));
+		assert_eq!(secs("2025-01-01T00:00:00Z"), Some(1_735_689_600));
+
+		for invalid in [
+			"",
+			"2025-1-01",
+			"2025-13-01",
+			"2025-02-29",
+			"1969-12-31",
+			"2025-01-01T00:00:00",
+			"2025-01-01T24:00:00Z",
+			"2025-01-01 00:00:00Z",
+			"+025-01-01",
+		] {
+			assert_eq!(secs(invalid), None, "{invalid}");
+		}
+	}
+
+	#[test]
+	fn test_from_raw() {
+		let keyring = Keyring::from_raw(
+			raw(&[
+				("a", KEY_A, None, false),
+				("b", KEY_B, Some("2025-01-01"), true),
+			]),
+			false,
+			SystemTime::now(),
+		)
+		.unwrap();
+
+		assert_eq!(keyring.primary().id, "b");
+		assert_eq!(keyring.get("a"), Some(&KEY_A.parse().unwrap()));
+		assert_eq!(keyring.get("c"), None);
+
+		let before = parse_date("2024-12-31T23:59:59Z").unwrap();
+		let after = parse_date("2025-01-01").unwrap();
+		let ids = |time| {
+			keyring
+				.active_at(time)
+				.map(|entry| entry.id.as_str())
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(ids(before), ["a"]);
+		assert_eq!(ids(after), ["a", "b"]);
+
+		// keys are redacted
+		let debug = format!("{keyring:?}");
+		assert!(debug.contains("\"b\""));
+		assert!(!debug.contains(&KEY_A[..8]));
+		assert!(!debug.contains(&0x5f1c_9a0e_7d3b_2846_u64.to_string()));
+	}
+
+	#[test]
+	fn test_from_raw_errors() {
+		let example = "0123456789abcdeffedcba9876543210";
+		let cases = [
+			(raw(&[]), KeyringError::Empty),
+			(raw(&[("a", KEY_A, None, false)]), KeyringError::NoPrimary),
+			(
+				raw(&[("a", KEY_A, None, true), ("b", KEY_B, None, true)]),
+				KeyringError::MultiplePrimary,
+			),
+			(
+				raw(&[("a", KEY_A, None, true), ("a", KEY_B, None, false)]),
+				KeyringError::DuplicateId("a".into()),
+			),
+			(
+				raw(&[("a", "5f1c", None, true)]),
+				KeyringError::InvalidKey("a".into(), KeyParseError::InvalidLength),
+			),
+			(
+				raw(&[("a", example, None, true)]),
+				KeyringError::WeakKey("a".into(), WeakKeyError::ExampleKey),
+			),
+			(
+				raw(&[("a", KEY_A, Some("soon"), true)]),
+				KeyringError::InvalidDate("a".into()),
+			),
+			(
+				raw(&[
+					("a", KEY_A, None, false),
+					("b", KEY_B, Some("2025-01-02"), true),
+				]),
+				KeyringError::PrimaryNotActive("b".into()),
+			),
+		];
+
+		let now = parse_date("2025-01-01T12:00:00Z").unwrap();
+		for (raw, expected) in cases {
+			let err = Keyring::from_raw(raw, false, now).unwrap_err();
+			assert!(!err.to_string().contains(example));
+			assert_eq!(err, expected);
+		}
+
+		assert!(Keyring::from_raw(raw(&[("a", example, None, true)]), true, now).is_ok());
+	}
+}
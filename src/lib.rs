@@ -3,10 +3,10 @@
 //!
 //! # Features
 //!
-//! - **Safe**: Implemented all APIs in safe Rust.
+//! - **Safe**: Implemented all APIs in safe Rust, except the optional C ABI (`capi` feature).
 //! - **Easy**: Provides simple object-oriented API.
 //! - **Fast**: Designed to eliminate overhead and run at high performance.
-//! - **Lightweight** Zero dependencies.
+//! - **Lightweight** Zero dependencies by default.
 //!
 //! # Example
 //!
@@ -51,16 +51,145 @@
 #![warn(clippy::all)]
 #![warn(clippy::cargo)]
 
+mod authenticated;
+mod builder;
+#[cfg(feature = "capi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capi")))]
+pub mod capi;
+#[cfg(feature = "diesel")]
+mod diesel_support;
+mod encoding;
 mod error;
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+mod facade_id;
+mod feistel;
+#[cfg(feature = "async-graphql")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-graphql")))]
+pub mod graphql;
+#[cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))]
+mod kdf;
 mod key;
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+mod keyring;
+mod loader;
+mod migration;
+mod prefixed;
+mod prf;
+#[cfg(feature = "prost")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prost")))]
+pub mod proto;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub mod python;
+mod range;
+#[cfg(feature = "rusqlite")]
+mod rusqlite_support;
+mod scheme;
+mod sentinel;
+mod siphash;
+#[cfg(any(
+	feature = "sqlx-postgres",
+	feature = "sqlx-mysql",
+	feature = "sqlx-sqlite"
+))]
+mod sqlx_support;
+#[cfg(feature = "tower")]
+mod tower_support;
+mod ulid;
 mod utils;
 mod uuid;
+#[cfg(feature = "v8-facade")]
+mod v8;
+mod vectors;
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub mod wasm;
 
-pub use error::{UuidParseError, UuidValidationError};
+pub use authenticated::{AuthenticatedFacade, MAX_TAG_BITS, MIN_TAG_BITS};
+pub use builder::UuidV7Builder;
+pub use encoding::{BASE32_LEN, BASE58_LEN, BASE64URL_LEN};
+#[cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))))]
+pub use error::KdfError;
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "keyring-json", feature = "keyring-toml")))
+)]
+pub use error::KeyringError;
+#[cfg(feature = "v8-facade")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v8-facade")))]
+pub use error::V8FacadeError;
+pub use error::{
+	AuthenticatedFacadeError, Base32Error, Base58Error, Base64UrlError, KeyLoadError,
+	KeyParseError, MappingTableError, PrefixedIdError, TestVectorError, UlidError, UuidParseError,
+	UuidValidationError, WeakKeyError,
+};
+#[cfg(all(
+	any(feature = "axum", feature = "actix-web"),
+	any(feature = "keyring-json", feature = "keyring-toml")
+))]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(all(
+		any(feature = "axum", feature = "actix-web"),
+		any(feature = "keyring-json", feature = "keyring-toml")
+	)))
+)]
+pub use facade_id::FacadeIdCandidates;
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "axum", feature = "actix-web"))))]
+pub use facade_id::{FacadeId, FacadeIdRejection, FacadeQuery, FacadeQueryParam, IdParam};
+#[cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "kdf-argon2", feature = "kdf-scrypt"))))]
+pub use kdf::{KdfParams, KDF_MIN_SALT_LEN};
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "async-graphql"))]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "axum", feature = "actix-web", feature = "async-graphql")))
+)]
+pub use key::FacadeKey;
 pub use key::UuidV47Key;
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+#[cfg_attr(
+	docsrs,
+	doc(cfg(any(feature = "keyring-json", feature = "keyring-toml")))
+)]
+pub use keyring::{Keyring, KeyringEntry};
+pub use loader::{KeyLoader, KeySource, KEY_FILE_MAX_LEN};
+pub use migration::{LegacyUuid, MappingEntry, MappingTable, MAPPING_TABLE_HEADER};
+pub use prefixed::{PrefixedId, PREFIX_MAX_LEN};
+#[cfg(feature = "prf-aes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-aes")))]
+pub use prf::Aes128Prf;
+#[cfg(feature = "prf-blake3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-blake3")))]
+pub use prf::Blake3Prf;
+pub use prf::FacadePrf;
+#[cfg(feature = "prf-hmac-sha256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-hmac-sha256")))]
+pub use prf::HmacSha256Prf;
+#[cfg(feature = "prf-siphash13")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-siphash13")))]
+pub use prf::SipHash13Prf;
+pub use range::UuidV7Range;
+pub use scheme::FacadeScheme;
+pub use sentinel::SentinelPolicy;
+pub use siphash::SipHasher24;
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub use tower_support::{FacadeJson, FacadeJsonBody, FacadeJsonLayer};
 pub use uuid::Uuid128;
+#[cfg(feature = "v8-facade")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v8-facade")))]
+pub use v8::V8_MAX_KEY_ID;
+pub use vectors::{test_vectors, TestVector, TEST_VECTORS_CSV};
 
 /// Re-export of common types for convenience.
 pub mod prelude {
-	pub use crate::{Uuid128, UuidParseError, UuidV47Key, UuidValidationError};
+	pub use crate::{
+		Base32Error, Base58Error, Base64UrlError, FacadePrf, FacadeScheme, PrefixedId,
+		PrefixedIdError, SentinelPolicy, Uuid128, UuidParseError, UuidV47Key, UuidV7Builder,
+		UuidV7Range, UuidValidationError,
+	};
 }
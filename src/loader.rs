@@ -0,0 +1,322 @@
+//! Loading keys from the environment and from files.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::error::{KeyLoadError, KeyParseError};
+use crate::key::UuidV47Key;
+#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+use crate::keyring::Keyring;
+
+/// Maximum size of a key or keyring file, larger files are refused with [`KeyLoadError::TooLarge`].
+pub const KEY_FILE_MAX_LEN: usize = 64 * 1024;
+
+/// A place a key is read from, reported by [`KeyLoadError`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum KeySource {
+	/// An environment variable.
+	Env(String),
+
+	/// A file, such as a mounted secret.
+	File(PathBuf),
+}
+
+impl std::fmt::Display for KeySource {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			KeySource::Env(name) => write!(f, "environment variable {name}"),
+			KeySource::File(path) => write!(f, "file {}", path.display()),
+		}
+	}
+}
+
+/// Loads a `UuidV47Key` from the first available source.
+///
+/// A key is 32 hex digits (`k0` then `k1`), surrounding whitespace is trimmed.<br>
+/// Sources are tried in the order they were added: an unset variable or a missing file falls through to the next one,
+/// any other failure stops the lookup, so a broken secret is never silently replaced.
+///
+/// By default weak keys (see [`UuidV47Key::validate`]) are refused,
+/// and on Unix files writable by group or others are refused, as anyone could swap the key.<br>
+/// Readable files are accepted, so mounted secrets work with their default modes (0444 for Docker, 0644 for Kubernetes):
+/// restrict who can read them through the mount or the directory.
+///
+/// # Examples
+/// ```no_run
+/// use uuid47::KeyLoader;
+///
+/// let key = KeyLoader::new()
+///     .env("UUID47_KEY")
+///     .file("/run/secrets/uuid47_key")
+///     .load()?;
+/// # Ok::<(), uuid47::KeyLoadError>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct KeyLoader {
+	sources: Vec<KeySource>,
+	allow_weak_keys: bool,
+	allow_insecure_permissions: bool,
+}
+
+impl KeyLoader {
+	/// Creates a loader without any source.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Read the key from an environment variable.
+	#[must_use]
+	pub fn env(mut self, name: impl Into<String>) -> Self {
+		self.sources.push(KeySource::Env(name.into()));
+		self
+	}
+
+	/// Read the key from a file.
+	#[must_use]
+	pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+		self.sources.push(KeySource::File(path.into()));
+		self
+	}
+
+	/// Accept keys rejected by [`UuidV47Key::validate`].
+	#[must_use]
+	pub fn allow_weak_keys(mut self) -> Self {
+		self.allow_weak_keys = true;
+		self
+	}
+
+	/// Accept files writable by group or others.
+	#[must_use]
+	pub fn allow_insecure_permissions(mut self) -> Self {
+		self.allow_insecure_permissions = true;
+		self
+	}
+
+	/// Load the key from the first source that is set.
+	///
+	/// # Errors
+	///
+	/// Returns [`KeyLoadError::NotFound`] if no source is set,
+	/// or the error of the first source that is set but cannot be loaded.
+	pub fn load(&self) -> Result<UuidV47Key, KeyLoadError> {
+		for source in &self.sources {
+			let Some(text) = self.read(source)? else {
+				continue;
+			};
+
+			let key: UuidV47Key = text
+				.parse()
+				.map_err(|err| KeyLoadError::Parse(source.clone(), err))?;
+			if !self.allow_weak_keys {
+				key.validate()
+					.map_err(|err| KeyLoadError::WeakKey(source.clone(), err))?;
+			}
+
+			return Ok(key);
+		}
+
+		Err(KeyLoadError::NotFound)
+	}
+
+	/// Load a keyring file, see [`Keyring`] for the format.
+	///
+	/// The format is chosen by the file extension (`.json` or `.toml`).
+	/// The sources of this loader are not used, only its options.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the file cannot be read or the keyring is invalid.
+	#[cfg(any(feature = "keyring-json", feature = "keyring-toml"))]
+	#[cfg_attr(
+		docsrs,
+		doc(cfg(any(feature = "keyring-json", feature = "keyring-toml")))
+	)]
+	pub fn load_keyring(&self, path: impl AsRef<Path>) -> Result<Keyring, KeyLoadError> {
+		let source = KeySource::File(path.as_ref().to_path_buf());
+		let Some(text) = self.read(&source)? else {
+			return Err(KeyLoadError::NotFound);
+		};
+
+		let extension = path.as_ref().extension().and_then(|ext| ext.to_str());
+		Keyring::parse(&text, extension, self.allow_weak_keys)
+			.map_err(|err| KeyLoadError::Keyring(source, err))
+	}
+
+	/// Read the source, `None` if it is not set.
+	fn read(&self, source: &KeySource) -> Result<Option<String>, KeyLoadError> {
+		match source {
+			KeySource::Env(name) => match std::env::var(name) {
+				Ok(value) => Ok(Some(value)),
+				Err(std::env::VarError::NotPresent) => Ok(None),
+				Err(std::env::VarError::NotUnicode(_)) => Err(KeyLoadError::Parse(
+					source.clone(),
+					KeyParseError::InvalidCharacter,
+				)),
+			},
+			KeySource::File(path) => self.read_file(source, path),
+		}
+	}
+
+	fn read_file(&self, source: &KeySource, path: &Path) -> Result<Option<String>, KeyLoadError> {
+		let io_error = |err: std::io::Error| KeyLoadError::Io(source.clone(), err.kind());
+
+		let file = match File::open(path) {
+			Ok(file) => file,
+			Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+			Err(err) => return Err(io_error(err)),
+		};
+
+		#[cfg(unix)]
+		if !self.allow_insecure_permissions {
+			use std::os::unix::fs::PermissionsExt;
+
+			// check the opened file, not the path, so it cannot be swapped in between
+			let mode = file.metadata().map_err(io_error)?.permissions().mode() & 0o777;
+			if mode & 0o022 != 0 {
+				return Err(KeyLoadError::InsecurePermissions(source.clone(), mode));
+			}
+		}
+
+		// read one byte past the limit to tell a file of exactly the limit from a larger one
+		let mut bytes = Vec::new();
+		file.take(KEY_FILE_MAX_LEN as u64 + 1)
+			.read_to_end(&mut bytes)
+			.map_err(io_error)?;
+		if bytes.len() > KEY_FILE_MAX_LEN {
+			return Err(KeyLoadError::TooLarge(source.clone()));
+		}
+		String::from_utf8(bytes)
+			.map(Some)
+			.map_err(|_| KeyLoadError::Parse(source.clone(), KeyParseError::InvalidCharacter))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::WeakKeyError;
+
+	const KEY_HEX: &str = "5f1c9a0e7d3b2846b4e8126fa0c37d95";
+
+	fn key() -> UuidV47Key {
+		UuidV47Key::new(0x5f1c_9a0e_7d3b_2846, 0xb4e8_126f_a0c3_7d95)
+	}
+
+	/// Write a file readable by the owner only.
+	fn write_secret(name: &str, contents: &str) -> PathBuf {
+		let path =
+			std::env::temp_dir().join(format!("uuid47-loader-{}-{name}", std::process::id()));
+		std::fs::write(&path, contents).unwrap();
+
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+		}
+
+		path
+	}
+
+	#[test]
+	fn test_load_env() {
+		std::env::set_var("UUID47_TEST_LOADER_ENV", format!("{KEY_HEX}\n"));
+
+		let loader = KeyLoader::new()
+			.env("UUID47_TEST_LOADER_UNSET")
+			.env("UUID47_TEST_LOADER_ENV");
+		assert_eq!(loader.load(), Ok(key()));
+
+		assert_eq!(
+			KeyLoader::new().env("UUID47_TEST_LOADER_UNSET").load(),
+			Err(KeyLoadError::NotFound)
+		);
+	}
+
+	#[test]
+	fn test_load_file() {
+		let path = write_secret("file", &format!("  {KEY_HEX}\r\n"));
+		let missing = std::env::temp_dir().join("uuid47-loader-missing");
+
+		let loader = KeyLoader::new().file(&missing).file(&path);
+		assert_eq!(loader.load(), Ok(key()));
+
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn test_load_errors() {
+		let source = KeySource::Env("UUID47_TEST_LOADER_BAD".into());
+
+		std::env::set_var("UUID47_TEST_LOADER_BAD", "not a key");
+		let err = KeyLoader::new()
+			.env("UUID47_TEST_LOADER_BAD")
+			.env("UUID47_TEST_LOADER_UNSET")
+			.load()
+			.unwrap_err();
+		assert_eq!(
+			err,
+			KeyLoadError::Parse(source.clone(), KeyParseError::InvalidLength)
+		);
+		assert_eq!(
+			err.to_string(),
+			"Invalid key in environment variable UUID47_TEST_LOADER_BAD: Key must be 32 hex digits"
+		);
+
+		let example = "0123456789abcdeffedcba9876543210";
+		std::env::set_var("UUID47_TEST_LOADER_WEAK", example);
+		let loader = KeyLoader::new().env("UUID47_TEST_LOADER_WEAK");
+		let err = loader.load().unwrap_err();
+		assert_eq!(
+			err,
+			KeyLoadError::WeakKey(
+				KeySource::Env("UUID47_TEST_LOADER_WEAK".into()),
+				WeakKeyError::ExampleKey
+			)
+		);
+		assert!(!err.to_string().contains(example));
+		assert!(loader.allow_weak_keys().load().is_ok());
+	}
+
+	#[test]
+	fn test_load_too_large() {
+		let path = write_secret("large", &" ".repeat(KEY_FILE_MAX_LEN + 1));
+
+		assert_eq!(
+			KeyLoader::new().file(&path).load(),
+			Err(KeyLoadError::TooLarge(KeySource::File(path.clone())))
+		);
+
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_load_insecure_permissions() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let path = write_secret("insecure", KEY_HEX);
+		let loader = KeyLoader::new().file(&path);
+
+		// default modes of Docker and Kubernetes secrets
+		for mode in [0o444, 0o644] {
+			std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+			assert_eq!(loader.load(), Ok(key()), "{mode:o}");
+		}
+
+		for mode in [0o664, 0o646] {
+			std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+			assert_eq!(
+				loader.load(),
+				Err(KeyLoadError::InsecurePermissions(
+					KeySource::File(path.clone()),
+					mode
+				))
+			);
+		}
+		assert_eq!(loader.allow_insecure_permissions().load(), Ok(key()));
+
+		std::fs::remove_file(path).unwrap();
+	}
+}
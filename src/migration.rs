@@ -0,0 +1,341 @@
+use crate::builder::UuidV7Builder;
+use crate::error::{MappingTableError, UuidParseError, UuidValidationError};
+use crate::key::UuidV47Key;
+use crate::siphash::siphash24;
+use crate::uuid::{parse_hyphenated, write_hyphenated, Uuid128};
+
+/// 100-nanosecond intervals between the Gregorian epoch (1582-10-15) and the Unix epoch.
+const GREGORIAN_UNIX_OFFSET: u64 = 0x01B2_1DD2_1381_4000;
+
+/// Header line of the [`MappingTable`] CSV format.
+pub const MAPPING_TABLE_HEADER: &str = "legacy,v7";
+
+/// A legacy time-based UUID (`UUIDv1` or `UUIDv6`), for migrating into `UUIDv7`.
+///
+/// Accepts the time-based versions rejected by [`Uuid128::from_bytes`],
+/// and [`LegacyUuid::to_v7`] derives a deterministic `UUIDv7`:
+///
+/// * the timestamp is the legacy 60-bit Gregorian timestamp converted to Unix milliseconds.
+/// * the 74 random bits come from SipHash-2-4 of the legacy UUID with a migration key.
+///
+/// The hash is one-way, so keep a [`MappingTable`] of every converted ID for audits.
+///
+/// # Examples
+/// ```
+/// use uuid47::{LegacyUuid, MappingTable, UuidV47Key};
+///
+/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+/// let legacy: LegacyUuid = "c232ab00-9414-11ec-b3c8-9f6bdeced846".parse().unwrap();
+///
+/// let v7 = legacy.to_v7(&key);
+/// assert_eq!(v7.timestamp_ms(), 1_645_557_742_000);
+///
+/// let mut table = MappingTable::new();
+/// table.push(legacy, &key);
+/// assert_eq!(table.find_legacy(&v7), Some(legacy));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct LegacyUuid {
+	bytes: [u8; 16],
+}
+
+impl LegacyUuid {
+	/// Create a legacy UUID from raw 16 bytes.
+	///
+	/// # Errors
+	///
+	/// * [`UuidValidationError::InvalidVersion`] - if the version is not 1 or 6.
+	/// * [`UuidValidationError::InvalidVariant`] - if the variant is not RFC 4122.
+	pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, UuidValidationError> {
+		if !matches!(bytes[6] >> 4, 1 | 6) {
+			return Err(UuidValidationError::InvalidVersion);
+		}
+		if (bytes[8] & 0xC0) != 0x80 {
+			return Err(UuidValidationError::InvalidVariant);
+		}
+
+		Ok(Self { bytes })
+	}
+
+	/// Get the raw 16 bytes.
+	#[must_use]
+	pub fn as_bytes(&self) -> &[u8; 16] {
+		&self.bytes
+	}
+
+	/// Get the UUID version (1 or 6).
+	#[must_use]
+	pub fn uuid_version(&self) -> u8 {
+		self.bytes[6] >> 4
+	}
+
+	/// Get the 60-bit timestamp, in 100-nanosecond intervals since 1582-10-15.
+	#[must_use]
+	pub fn gregorian_timestamp(&self) -> u64 {
+		let b = &self.bytes;
+		let word = |i: usize, n: usize| {
+			b[i..i + n]
+				.iter()
+				.fold(0u64, |acc, &x| (acc << 8) | u64::from(x))
+		};
+
+		if self.uuid_version() == 1 {
+			// time_low (32) | time_mid (16) | version (4) time_high (12)
+			((word(6, 2) & 0x0FFF) << 48) | (word(4, 2) << 32) | word(0, 4)
+		} else {
+			// time_high (32) | time_mid (16) | version (4) time_low (12)
+			(word(0, 4) << 28) | (word(4, 2) << 12) | (word(6, 2) & 0x0FFF)
+		}
+	}
+
+	/// Get the timestamp as Unix milliseconds.
+	///
+	/// Timestamps before 1970-01-01 saturate to 0.
+	#[must_use]
+	pub fn timestamp_ms(&self) -> u64 {
+		self.gregorian_timestamp()
+			.saturating_sub(GREGORIAN_UNIX_OFFSET)
+			/ 10_000
+	}
+
+	/// Derive the `UUIDv7` for this legacy UUID.
+	///
+	/// Deterministic for a given migration key, the random bits are SipHash-2-4 of the 16 bytes.
+	#[must_use]
+	pub fn to_v7(&self, key: &UuidV47Key) -> Uuid128 {
+		// domain separation from the facade and Feistel messages
+		let mut msg = [0u8; 20];
+		msg[0..4].copy_from_slice(b"u47m");
+		msg[4..20].copy_from_slice(&self.bytes);
+
+		let rand_b = siphash24(&msg, key.k0, key.k1);
+		msg[3] = b'a';
+		#[allow(clippy::cast_possible_truncation)]
+		let rand_a = siphash24(&msg, key.k0, key.k1) as u16;
+
+		UuidV7Builder::new()
+			.timestamp_ms(self.timestamp_ms())
+			.rand_a(rand_a)
+			.rand_b(rand_b)
+			.build()
+	}
+}
+
+impl TryFrom<[u8; 16]> for LegacyUuid {
+	type Error = UuidValidationError;
+
+	fn try_from(bytes: [u8; 16]) -> Result<Self, Self::Error> {
+		Self::from_bytes(bytes)
+	}
+}
+
+impl std::str::FromStr for LegacyUuid {
+	type Err = UuidParseError;
+
+	/// Parse a 8-4-4-4-12 hex string of a `UUIDv1` or `UUIDv6`.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let bytes = parse_hyphenated(s)?;
+
+		Self::from_bytes(bytes).map_err(|_| UuidParseError::InvalidHex)
+	}
+}
+
+impl std::fmt::Display for LegacyUuid {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write_hyphenated(&self.bytes, f)
+	}
+}
+
+/// A row of a [`MappingTable`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct MappingEntry {
+	/// The original `UUIDv1` / `UUIDv6`.
+	pub legacy: LegacyUuid,
+
+	/// The derived `UUIDv7`.
+	pub v7: Uuid128,
+}
+
+/// Mapping between legacy UUID and their derived `UUIDv7`, for audits.
+///
+/// Serialized as CSV with the header `legacy,v7` and one `legacy,v7` pair per line.
+/// Lines starting with `#` are comments.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MappingTable {
+	entries: Vec<MappingEntry>,
+}
+
+impl MappingTable {
+	/// Creates an empty table.
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Convert `legacy` with [`LegacyUuid::to_v7`], record the pair and return the `UUIDv7`.
+	pub fn push(&mut self, legacy: LegacyUuid, key: &UuidV47Key) -> Uuid128 {
+		let v7 = legacy.to_v7(key);
+		self.entries.push(MappingEntry { legacy, v7 });
+		v7
+	}
+
+	/// Get the recorded pairs.
+	#[must_use]
+	pub fn entries(&self) -> &[MappingEntry] {
+		&self.entries
+	}
+
+	/// Find the legacy UUID of a derived `UUIDv7`.
+	#[must_use]
+	pub fn find_legacy(&self, v7: &Uuid128) -> Option<LegacyUuid> {
+		self.entries
+			.iter()
+			.find(|entry| entry.v7 == *v7)
+			.map(|entry| entry.legacy)
+	}
+
+	/// Find the derived `UUIDv7` of a legacy UUID.
+	#[must_use]
+	pub fn find_v7(&self, legacy: &LegacyUuid) -> Option<Uuid128> {
+		self.entries
+			.iter()
+			.find(|entry| entry.legacy == *legacy)
+			.map(|entry| entry.v7)
+	}
+
+	/// Check that every recorded `UUIDv7` is derived from its legacy UUID with `key`.
+	///
+	/// # Errors
+	///
+	/// * [`MappingTableError::Mismatch`] - with the 1-based index of the first mismatching entry.
+	pub fn verify(&self, key: &UuidV47Key) -> Result<(), MappingTableError> {
+		match self
+			.entries
+			.iter()
+			.position(|entry| entry.legacy.to_v7(key) != entry.v7)
+		{
+			Some(index) => Err(MappingTableError::Mismatch(index + 1)),
+			None => Ok(()),
+		}
+	}
+
+	/// Serialize as CSV.
+	#[must_use]
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::with_capacity((self.entries.len() + 1) * 74);
+		csv.push_str(MAPPING_TABLE_HEADER);
+		csv.push('\n');
+		for entry in &self.entries {
+			csv.push_str(&format!("{},{}\n", entry.legacy, entry.v7));
+		}
+		csv
+	}
+
+	/// Parse a CSV table produced by [`MappingTable::to_csv`].
+	///
+	/// # Errors
+	///
+	/// * [`MappingTableError::InvalidHeader`] - if the first line is not `legacy,v7`.
+	/// * [`MappingTableError::InvalidLine`] - with the 1-based line number of a malformed line.
+	pub fn from_csv(csv: &str) -> Result<Self, MappingTableError> {
+		let mut lines = csv
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+		match lines.next() {
+			Some((_, MAPPING_TABLE_HEADER)) => {}
+			_ => return Err(MappingTableError::InvalidHeader),
+		}
+
+		let entries = lines
+			.map(|(i, line)| {
+				let invalid = MappingTableError::InvalidLine(i + 1);
+				let (legacy, v7) = line.split_once(',').ok_or(invalid)?;
+
+				Ok(MappingEntry {
+					legacy: legacy.parse().map_err(|_| invalid)?,
+					v7: v7.parse().map_err(|_| invalid)?,
+				})
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self { entries })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// RFC 9562 appendix A examples, both 2022-02-22 19:22:22 UTC
+	const V1: &str = "c232ab00-9414-11ec-b3c8-9f6bdeced846";
+	const V6: &str = "1ec9414c-232a-6b00-b3c8-9f6bdeced846";
+
+	#[test]
+	fn test_legacy_timestamps() {
+		let v1: LegacyUuid = V1.parse().unwrap();
+		let v6: LegacyUuid = V6.parse().unwrap();
+
+		assert_eq!(v1.uuid_version(), 1);
+		assert_eq!(v6.uuid_version(), 6);
+		assert_eq!(v1.gregorian_timestamp(), 0x1EC_9414_C232_AB00);
+		assert_eq!(v6.gregorian_timestamp(), 0x1EC_9414_C232_AB00);
+		assert_eq!(v1.timestamp_ms(), 1_645_557_742_000);
+		assert_eq!(v1.to_string(), V1);
+
+		// v4 / v7 are rejected
+		assert!(matches!(
+			LegacyUuid::from_bytes(Uuid128::empty().into_bytes()),
+			Err(UuidValidationError::InvalidVersion)
+		));
+	}
+
+	#[test]
+	fn test_to_v7() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		let v1: LegacyUuid = V1.parse().unwrap();
+		let v6: LegacyUuid = V6.parse().unwrap();
+
+		let a = v1.to_v7(&key);
+		assert_eq!(a.uuid_version(), 7);
+		assert_eq!(a.timestamp_ms(), v1.timestamp_ms());
+		assert_eq!(a, v1.to_v7(&key));
+
+		// same timestamp, different bits
+		assert_ne!(a, v6.to_v7(&key));
+		assert_ne!(a, v1.to_v7(&UuidV47Key::new(1, 2)));
+	}
+
+	#[test]
+	fn test_mapping_table() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		let v1: LegacyUuid = V1.parse().unwrap();
+		let v6: LegacyUuid = V6.parse().unwrap();
+
+		let mut table = MappingTable::new();
+		let a = table.push(v1, &key);
+		let b = table.push(v6, &key);
+		assert_eq!(table.find_legacy(&b), Some(v6));
+		assert_eq!(table.find_v7(&v1), Some(a));
+
+		let csv = table.to_csv();
+		assert!(csv.starts_with("legacy,v7\nc232ab00-9414-11ec-b3c8-9f6bdeced846,"));
+		let parsed = MappingTable::from_csv(&csv).unwrap();
+		assert_eq!(parsed, table);
+		assert_eq!(parsed.verify(&key), Ok(()));
+		assert_eq!(
+			parsed.verify(&UuidV47Key::new(1, 2)),
+			Err(MappingTableError::Mismatch(1))
+		);
+
+		assert_eq!(
+			MappingTable::from_csv("v7,legacy\n"),
+			Err(MappingTableError::InvalidHeader)
+		);
+		assert_eq!(
+			MappingTable::from_csv(&format!("# audit\nlegacy,v7\n{V1},{V6}\n")),
+			Err(MappingTableError::InvalidLine(3))
+		);
+	}
+}
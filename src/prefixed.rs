@@ -0,0 +1,260 @@
+use crate::error::PrefixedIdError;
+use crate::key::UuidV47Key;
+use crate::sentinel::SentinelPolicy;
+use crate::uuid::Uuid128;
+
+/// Maximum length of a [`PrefixedId`] prefix.
+pub const PREFIX_MAX_LEN: usize = 63;
+
+/// TypeID-style public identifier: a type prefix and the base32 form of a `UUIDv4` facade.
+///
+/// Formatted as `prefix_suffix` (or just `suffix` with an empty prefix), following the
+/// [TypeID specification](https://github.com/jetify-com/typeid/tree/main/spec):
+///
+/// * the prefix is 0 to 63 lowercase ASCII letters and underscores, starting and ending with a letter.
+/// * the suffix is the 26-character lowercase Crockford base32 form of the UUID.
+///
+/// Decoding checks the prefix, so an ID of one entity type cannot be used for another.
+///
+/// # Examples
+/// ```
+/// use uuid47::{PrefixedId, Uuid128, UuidV47Key};
+///
+/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+/// let v7 = Uuid128::empty();
+///
+/// let id = PrefixedId::encode("user", &v7, &key).unwrap();
+/// assert_eq!(id.to_string(), "user_12v5rjd5g98008000000000000");
+///
+/// let parsed: PrefixedId = "user_12v5rjd5g98008000000000000".parse().unwrap();
+/// assert_eq!(parsed.decode("user", &key), Ok(v7));
+/// assert!(parsed.decode("order", &key).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct PrefixedId {
+	prefix: String,
+	facade: Uuid128,
+}
+
+impl PrefixedId {
+	/// Creates a prefixed ID from a prefix and a `UUIDv4` facade.
+	///
+	/// # Errors
+	///
+	/// * [`PrefixedIdError::InvalidPrefix`] - if the prefix is not valid.
+	pub fn new(prefix: &str, facade: Uuid128) -> Result<Self, PrefixedIdError> {
+		validate_prefix(prefix)?;
+
+		Ok(Self {
+			prefix: prefix.to_owned(),
+			facade,
+		})
+	}
+
+	/// Encode a `UUIDv7` into `UUIDv4` facade and attach the prefix.
+	///
+	/// # Errors
+	///
+	/// * [`PrefixedIdError::InvalidPrefix`] - if the prefix is not valid.
+	pub fn encode(prefix: &str, v7: &Uuid128, key: &UuidV47Key) -> Result<Self, PrefixedIdError> {
+		Self::new(prefix, v7.encode_as_v4facade(key))
+	}
+
+	/// Encode a `UUIDv7` with the key selected by the prefix.
+	///
+	/// # Errors
+	///
+	/// * [`PrefixedIdError::InvalidPrefix`] - if the prefix is not valid.
+	/// * [`PrefixedIdError::UnknownPrefix`] - if `key_for` returns no key for the prefix.
+	pub fn encode_with<'k>(
+		prefix: &str,
+		v7: &Uuid128,
+		key_for: impl FnOnce(&str) -> Option<&'k UuidV47Key>,
+	) -> Result<Self, PrefixedIdError> {
+		validate_prefix(prefix)?;
+		let key = key_for(prefix).ok_or(PrefixedIdError::UnknownPrefix)?;
+
+		Self::encode(prefix, v7, key)
+	}
+
+	/// Check the prefix and decode the facade back into `UUIDv7`.
+	///
+	/// # Errors
+	///
+	/// * [`PrefixedIdError::PrefixMismatch`] - if the prefix is not `expected_prefix`.
+	/// * [`PrefixedIdError::NotFacade`] - if the UUID is not a `UUIDv4` facade.
+	pub fn decode(
+		&self,
+		expected_prefix: &str,
+		key: &UuidV47Key,
+	) -> Result<Uuid128, PrefixedIdError> {
+		if self.prefix != expected_prefix {
+			return Err(PrefixedIdError::PrefixMismatch);
+		}
+
+		self.decode_unchecked(key)
+	}
+
+	/// Decode the facade back into `UUIDv7` with the key selected by the prefix.
+	///
+	/// # Errors
+	///
+	/// * [`PrefixedIdError::UnknownPrefix`] - if `key_for` returns no key for the prefix.
+	/// * [`PrefixedIdError::NotFacade`] - if the UUID is not a `UUIDv4` facade.
+	pub fn decode_with<'k>(
+		&self,
+		key_for: impl FnOnce(&str) -> Option<&'k UuidV47Key>,
+	) -> Result<Uuid128, PrefixedIdError> {
+		let key = key_for(&self.prefix).ok_or(PrefixedIdError::UnknownPrefix)?;
+
+		self.decode_unchecked(key)
+	}
+
+	fn decode_unchecked(&self, key: &UuidV47Key) -> Result<Uuid128, PrefixedIdError> {
+		self.facade
+			.checked_decode_from_v4facade(key, SentinelPolicy::Passthrough)
+			.map_err(|_| PrefixedIdError::NotFacade)
+	}
+
+	/// Get the prefix, empty if none.
+	#[must_use]
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// Get the `UUIDv4` facade.
+	#[must_use]
+	pub fn facade(&self) -> Uuid128 {
+		self.facade
+	}
+}
+
+fn validate_prefix(prefix: &str) -> Result<(), PrefixedIdError> {
+	let bytes = prefix.as_bytes();
+	if bytes.is_empty() {
+		return Ok(());
+	}
+
+	let valid = bytes.len() <= PREFIX_MAX_LEN
+		&& bytes.iter().all(|&c| c.is_ascii_lowercase() || c == b'_')
+		&& bytes[0] != b'_'
+		&& bytes[bytes.len() - 1] != b'_';
+
+	if valid {
+		Ok(())
+	} else {
+		Err(PrefixedIdError::InvalidPrefix)
+	}
+}
+
+impl std::str::FromStr for PrefixedId {
+	type Err = PrefixedIdError;
+
+	/// Parse `prefix_suffix`, or a bare `suffix` for an empty prefix.
+	///
+	/// The separator is the last underscore, the suffix must be lowercase.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (prefix, suffix) = match s.rsplit_once('_') {
+			Some(("", _)) => return Err(PrefixedIdError::InvalidPrefix),
+			Some((prefix, suffix)) => (prefix, suffix),
+			None => ("", s),
+		};
+
+		if suffix.bytes().any(|c| c.is_ascii_uppercase()) {
+			return Err(PrefixedIdError::InvalidSuffix(
+				crate::error::Base32Error::InvalidCharacter,
+			));
+		}
+
+		let facade = Uuid128::from_base32(&suffix.to_ascii_uppercase())
+			.map_err(PrefixedIdError::InvalidSuffix)?;
+
+		Self::new(prefix, facade)
+	}
+}
+
+impl std::fmt::Display for PrefixedId {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut suffix = self.facade.to_base32();
+		suffix.make_ascii_lowercase();
+
+		if self.prefix.is_empty() {
+			f.write_str(&suffix)
+		} else {
+			write!(f, "{}_{suffix}", self.prefix)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::error::Base32Error;
+
+	#[test]
+	fn test_prefixed_id() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		let user_key = UuidV47Key::new(1, 2);
+		let v7 = Uuid128::empty();
+
+		// per-type key
+		let keys = |prefix: &str| match prefix {
+			"user" => Some(&user_key),
+			"" => Some(&key),
+			_ => None,
+		};
+		let id = PrefixedId::encode_with("user", &v7, keys).unwrap();
+		assert_eq!(id.facade(), v7.encode_as_v4facade(&user_key));
+		assert_eq!(id.to_string().parse::<PrefixedId>(), Ok(id.clone()));
+		assert_eq!(id.decode_with(keys), Ok(v7));
+		assert_eq!(
+			PrefixedId::encode_with("order", &v7, keys),
+			Err(PrefixedIdError::UnknownPrefix)
+		);
+
+		// no prefix, no separator
+		let bare = PrefixedId::encode("", &v7, &key).unwrap();
+		assert_eq!(bare.to_string(), "12v5rjd5g98008000000000000");
+		assert_eq!(bare.to_string().parse::<PrefixedId>(), Ok(bare.clone()));
+
+		// prefix with underscores, the separator is the last one
+		let id: PrefixedId = "api_key_12v5rjd5g98008000000000000".parse().unwrap();
+		assert_eq!(id.prefix(), "api_key");
+		assert_eq!(id.decode("api_key", &key), Ok(v7));
+
+		// not a facade
+		let id = PrefixedId::new("user", v7).unwrap();
+		assert_eq!(id.decode("user", &key), Err(PrefixedIdError::NotFacade));
+	}
+
+	#[test]
+	fn test_prefixed_id_invalid() {
+		let suffix = "12v5rjd5g98008000000000000";
+
+		for prefix in ["User", "_user", "user_", "us3r", "a".repeat(64).as_str()] {
+			assert_eq!(
+				format!("{prefix}_{suffix}").parse::<PrefixedId>(),
+				Err(PrefixedIdError::InvalidPrefix),
+				"{prefix}"
+			);
+		}
+		assert!(format!("{}_{suffix}", "a".repeat(63))
+			.parse::<PrefixedId>()
+			.is_ok());
+
+		assert_eq!(
+			"user_12V5RJD5G98008000000000000".parse::<PrefixedId>(),
+			Err(PrefixedIdError::InvalidSuffix(
+				Base32Error::InvalidCharacter
+			))
+		);
+		assert_eq!(
+			"user_8zzzzzzzzzzzzzzzzzzzzzzzzz".parse::<PrefixedId>(),
+			Err(PrefixedIdError::InvalidSuffix(Base32Error::Overflow))
+		);
+		assert_eq!(
+			"user_12v5rjd5g9800800000000000".parse::<PrefixedId>(),
+			Err(PrefixedIdError::InvalidSuffix(Base32Error::InvalidLength))
+		);
+	}
+}
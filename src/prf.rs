@@ -0,0 +1,311 @@
+//! Keyed PRF masking the timestamp of the reference transform.
+//!
+//! The facade timestamp is `ts48 ^ (prf(msg) & 0xFFFF_FFFF_FFFF)`, where `msg` is the 10 bytes
+//! `[rand_a (big-endian u16)][rand_b (big-endian u64)]` of the `UUIDv7`.<br>
+//! Only SipHash-2-4 ([`UuidV47Key`]) is compatible with other `UUIDv47` implementations,
+//! facades produced with another backend must be decoded with the same backend and key.
+//!
+//! The `*_with_prf`, `checked_*` and `min_for_v4facade` methods of `Uuid128` take any backend.
+//! [`FacadeScheme::FullWidthV1`](crate::FacadeScheme::FullWidthV1), [`AuthenticatedFacade`](crate::AuthenticatedFacade),
+//! `UUIDv8` facades, prefixed IDs and the integrations (web frameworks, tower, GraphQL, gRPC, bindings)
+//! always use the reference SipHash-2-4 with a `UuidV47Key`.
+
+use crate::key::UuidV47Key;
+use crate::siphash::siphash24;
+
+/// Keyed pseudo-random function used by [`Uuid128::encode_as_v4facade_with_prf`](crate::Uuid128::encode_as_v4facade_with_prf).
+///
+/// Only the low 48 bits of the output are used.<br>
+/// [`UuidV47Key`] implements this trait with SipHash-2-4, the reference PRF.
+///
+/// # Examples
+/// ```
+/// use uuid47::{FacadePrf, Uuid128, UuidV47Key};
+///
+/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+/// let v7 = Uuid128::empty();
+///
+/// assert_eq!(v7.encode_as_v4facade_with_prf(&key), v7.encode_as_v4facade(&key));
+/// ```
+pub trait FacadePrf {
+	/// Evaluate the PRF on the 10-byte message.
+	fn prf(&self, msg: &[u8; 10]) -> u64;
+}
+
+impl<P: FacadePrf + ?Sized> FacadePrf for &P {
+	#[inline]
+	fn prf(&self, msg: &[u8; 10]) -> u64 {
+		(**self).prf(msg)
+	}
+}
+
+impl FacadePrf for UuidV47Key {
+	/// SipHash-2-4 (reference).
+	#[inline]
+	fn prf(&self, msg: &[u8; 10]) -> u64 {
+		siphash24(msg, self.k0, self.k1)
+	}
+}
+
+/// SipHash-1-3 PRF.
+///
+/// Fewer rounds than the reference SipHash-2-4, for throughput over security margin.
+#[cfg(feature = "prf-siphash13")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-siphash13")))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct SipHash13Prf(pub UuidV47Key);
+
+#[cfg(feature = "prf-siphash13")]
+impl FacadePrf for SipHash13Prf {
+	#[inline]
+	fn prf(&self, msg: &[u8; 10]) -> u64 {
+		crate::siphash::siphash13(msg, self.0.k0, self.0.k1)
+	}
+}
+
+/// HMAC-SHA-256 PRF.
+///
+/// The output is the first 8 bytes of the MAC as little-endian `u64`.
+#[cfg(feature = "prf-hmac-sha256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-hmac-sha256")))]
+#[derive(Clone)]
+pub struct HmacSha256Prf {
+	mac: hmac::Hmac<sha2::Sha256>,
+}
+
+#[cfg(feature = "prf-hmac-sha256")]
+impl HmacSha256Prf {
+	/// Creates a PRF keyed with a 256-bit key.
+	#[must_use]
+	pub fn new(key: &[u8; 32]) -> Self {
+		use hmac::digest::KeyInit;
+
+		// HMAC pads keys shorter than the block size with zeros
+		let mut block = [0u8; 64];
+		block[..32].copy_from_slice(key);
+
+		Self {
+			mac: hmac::Hmac::new(&block.into()),
+		}
+	}
+}
+
+#[cfg(feature = "prf-hmac-sha256")]
+impl std::fmt::Debug for HmacSha256Prf {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("HmacSha256Prf").finish_non_exhaustive()
+	}
+}
+
+#[cfg(feature = "prf-hmac-sha256")]
+impl FacadePrf for HmacSha256Prf {
+	#[inline]
+	fn prf(&self, msg: &[u8; 10]) -> u64 {
+		use hmac::Mac;
+
+		let mut mac = self.mac.clone();
+		mac.update(msg);
+		let out = mac.finalize().into_bytes();
+		u64::from_le_bytes(out[0..8].try_into().unwrap())
+	}
+}
+
+/// AES-128 PRF.
+///
+/// The message is zero-padded to one block and encrypted,
+/// the output is the first 8 bytes of the ciphertext as little-endian `u64`.
+#[cfg(feature = "prf-aes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-aes")))]
+#[derive(Clone)]
+pub struct Aes128Prf {
+	cipher: aes::Aes128,
+}
+
+#[cfg(feature = "prf-aes")]
+impl Aes128Prf {
+	/// Creates a PRF keyed with a 128-bit key.
+	#[must_use]
+	pub fn new(key: &[u8; 16]) -> Self {
+		use aes::cipher::KeyInit;
+
+		Self {
+			cipher: aes::Aes128::new(key.into()),
+		}
+	}
+}
+
+#[cfg(feature = "prf-aes")]
+impl std::fmt::Debug for Aes128Prf {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Aes128Prf").finish_non_exhaustive()
+	}
+}
+
+#[cfg(feature = "prf-aes")]
+impl FacadePrf for Aes128Prf {
+	#[inline]
+	fn prf(&self, msg: &[u8; 10]) -> u64 {
+		use aes::cipher::BlockEncrypt;
+
+		let mut block = aes::Block::default();
+		block[0..10].copy_from_slice(msg);
+		self.cipher.encrypt_block(&mut block);
+		u64::from_le_bytes(block[0..8].try_into().unwrap())
+	}
+}
+
+/// BLAKE3 keyed-hash PRF.
+///
+/// The output is the first 8 bytes of the keyed hash as little-endian `u64`.
+#[cfg(feature = "prf-blake3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prf-blake3")))]
+#[derive(Clone)]
+pub struct Blake3Prf {
+	key: [u8; 32],
+}
+
+#[cfg(feature = "prf-blake3")]
+impl Blake3Prf {
+	/// Creates a PRF keyed with a 256-bit key.
+	#[must_use]
+	pub fn new(key: &[u8; 32]) -> Self {
+		Self { key: *key }
+	}
+}
+
+#[cfg(feature = "prf-blake3")]
+impl std::fmt::Debug for Blake3Prf {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Blake3Prf").finish_non_exhaustive()
+	}
+}
+
+#[cfg(feature = "prf-blake3")]
+impl FacadePrf for Blake3Prf {
+	#[inline]
+	fn prf(&self, msg: &[u8; 10]) -> u64 {
+		let out = blake3::keyed_hash(&self.key, msg);
+		u64::from_le_bytes(out.as_bytes()[0..8].try_into().unwrap())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::uuid::Uuid128;
+
+	/// The PRF message used by the vectors below.
+	const MSG: [u8; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+	/// `UUIDv7` inputs shared by every backend.
+	const V7: [&str; 3] = [
+		"00000000-0000-7000-8000-000000000000",
+		"01912345-6789-7abc-9def-0123456789ab",
+		"ffffffff-ffff-7fff-bfff-ffffffffffff",
+	];
+
+	#[cfg(any(feature = "prf-hmac-sha256", feature = "prf-blake3"))]
+	fn key32() -> [u8; 32] {
+		core::array::from_fn(|i| i as u8)
+	}
+
+	fn check<P: FacadePrf>(prf: &P, expected_prf: u64, expected: [&str; 3]) {
+		assert_eq!(prf.prf(&MSG), expected_prf);
+
+		for (v7, facade) in V7.iter().zip(expected) {
+			let v7: Uuid128 = v7.parse().unwrap();
+			let facade: Uuid128 = facade.parse().unwrap();
+
+			assert_eq!(v7.encode_as_v4facade_with_prf(prf), facade, "{v7}");
+			assert_eq!(facade.decode_from_v4facade_with_prf(prf), v7, "{v7}");
+		}
+	}
+
+	#[test]
+	fn test_siphash24_vectors() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+
+		check(
+			&key,
+			0xb632_3a28_a186_9df9,
+			[
+				"22d97126-9609-4000-8000-000000000000",
+				"f36a1da9-4d79-4abc-9def-0123456789ab",
+				"92e2af2b-ee16-4fff-bfff-ffffffffffff",
+			],
+		);
+	}
+
+	#[cfg(feature = "prf-siphash13")]
+	#[test]
+	fn test_siphash13_vectors() {
+		let prf = SipHash13Prf(UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210));
+
+		check(
+			&prf,
+			0x6392_a7b3_6b79_3ff8,
+			[
+				"0c3f4730-8cb0-4000-8000-000000000000",
+				"53f035c7-e14b-4abc-9def-0123456789ab",
+				"49e2e043-0803-4fff-bfff-ffffffffffff",
+			],
+		);
+
+		// reference SipHash-1-3 (key = 00..0f, message = empty and 00..0e)
+		let k0 = 0x0706_0504_0302_0100;
+		let k1 = 0x0f0e_0d0c_0b0a_0908;
+		let input: Vec<u8> = (0..15).collect();
+		assert_eq!(
+			crate::siphash::siphash13(&[], k0, k1),
+			0xabac_0158_050f_c4dc
+		);
+		assert_eq!(
+			crate::siphash::siphash13(&input, k0, k1),
+			0xd320_d86d_2a51_9956
+		);
+	}
+
+	#[cfg(feature = "prf-hmac-sha256")]
+	#[test]
+	fn test_hmac_sha256_vectors() {
+		check(
+			&HmacSha256Prf::new(&key32()),
+			0x446c_d315_cff8_8f0e,
+			[
+				"db0feb01-51ca-4000-8000-000000000000",
+				"2f496005-f974-4abc-9def-0123456789ab",
+				"75f48afe-64a2-4fff-bfff-ffffffffffff",
+			],
+		);
+	}
+
+	#[cfg(feature = "prf-aes")]
+	#[test]
+	fn test_aes128_vectors() {
+		let key: [u8; 16] = core::array::from_fn(|i| i as u8);
+
+		check(
+			&Aes128Prf::new(&key),
+			0x4bac_7c6c_6217_813c,
+			[
+				"8f87373b-a1c6-4000-8000-000000000000",
+				"62bb1e05-a6ea-4abc-9def-0123456789ab",
+				"e5831e24-e9b8-4fff-bfff-ffffffffffff",
+			],
+		);
+	}
+
+	#[cfg(feature = "prf-blake3")]
+	#[test]
+	fn test_blake3_vectors() {
+		check(
+			&Blake3Prf::new(&key32()),
+			0x620e_bf9b_33e0_20a9,
+			[
+				"a5bb7a10-4cf5-4000-8000-000000000000",
+				"eb0b5f13-fb85-4abc-9def-0123456789ab",
+				"1553d00f-29be-4fff-bfff-ffffffffffff",
+			],
+		);
+	}
+}
@@ -0,0 +1,247 @@
+//! Protobuf message and gRPC helpers.
+//!
+//! [`Uuid128`] mirrors `proto/uuid47.proto`, import that file from other services
+//! and convert with `TryFrom` / `From` to validate the 16 bytes on the way in.
+//!
+//! With the `tonic` feature, [`FacadeInterceptor`] decodes facade IDs sent in request metadata.
+//! Interceptors cannot see messages, so decode message fields with [`decode_facade`] in the handler.
+
+#[cfg(feature = "tonic")]
+use crate::error::UuidParseError;
+use crate::error::UuidValidationError;
+#[cfg(feature = "tonic")]
+use crate::error::WeakKeyError;
+#[cfg(feature = "tonic")]
+use crate::key::UuidV47Key;
+#[cfg(feature = "tonic")]
+use crate::sentinel::SentinelPolicy;
+
+/// `uuid47.Uuid128` protobuf message.
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct Uuid128 {
+	/// 16 bytes, big-endian.
+	#[prost(bytes = "vec", tag = "1")]
+	pub value: Vec<u8>,
+}
+
+impl From<crate::uuid::Uuid128> for Uuid128 {
+	fn from(uuid: crate::uuid::Uuid128) -> Self {
+		Self {
+			value: uuid.as_bytes().to_vec(),
+		}
+	}
+}
+
+impl TryFrom<&Uuid128> for crate::uuid::Uuid128 {
+	type Error = UuidValidationError;
+
+	/// Validates the bytes with [`crate::Uuid128::from_bytes`].
+	fn try_from(message: &Uuid128) -> Result<Self, Self::Error> {
+		crate::uuid::Uuid128::try_from(message.value.as_slice())
+	}
+}
+
+impl TryFrom<Uuid128> for crate::uuid::Uuid128 {
+	type Error = UuidValidationError;
+
+	/// Validates the bytes with [`crate::Uuid128::from_bytes`].
+	fn try_from(message: Uuid128) -> Result<Self, Self::Error> {
+		Self::try_from(&message)
+	}
+}
+
+#[cfg(feature = "tonic")]
+impl From<UuidParseError> for tonic::Status {
+	fn from(err: UuidParseError) -> Self {
+		tonic::Status::invalid_argument(err.to_string())
+	}
+}
+
+#[cfg(feature = "tonic")]
+impl From<UuidValidationError> for tonic::Status {
+	fn from(err: UuidValidationError) -> Self {
+		tonic::Status::invalid_argument(err.to_string())
+	}
+}
+
+/// Validate a message field and decode the `UUIDv4` facade it carries.
+///
+/// # Errors
+///
+/// * `INVALID_ARGUMENT` - if the bytes are not a valid UUID.
+/// * `NOT_FOUND` - if the UUID is not a `UUIDv4` facade (including Nil and Max).
+#[cfg(feature = "tonic")]
+pub fn decode_facade(
+	message: &Uuid128,
+	key: &UuidV47Key,
+) -> Result<crate::uuid::Uuid128, tonic::Status> {
+	crate::uuid::Uuid128::try_from(message)?
+		.checked_decode_from_v4facade(key, SentinelPolicy::Reject)
+		.map_err(|_| tonic::Status::not_found("Not found"))
+}
+
+/// Tonic interceptor decoding `UUIDv4` facades in request metadata.
+///
+/// Each configured ASCII metadata entry is replaced with the decoded `UUIDv7` string.
+///
+/// * Malformed UUID strings are rejected with `INVALID_ARGUMENT`.
+/// * Well-formed UUID that are not `UUIDv4` facades are rejected with `NOT_FOUND`.
+///
+/// # Examples
+/// ```
+/// use uuid47::proto::FacadeInterceptor;
+/// use uuid47::UuidV47Key;
+///
+/// let key = UuidV47Key::new(0x5f1c9a0e7d3b2846, 0xb4e8126fa0c37d95);
+/// let interceptor = FacadeInterceptor::new(key)?.with_metadata_key("x-resource-id");
+/// // MyServiceServer::with_interceptor(service, interceptor)
+/// # Ok::<(), uuid47::WeakKeyError>(())
+/// ```
+#[cfg(feature = "tonic")]
+#[derive(Debug, Clone)]
+pub struct FacadeInterceptor {
+	key: UuidV47Key,
+	metadata_keys: Vec<tonic::metadata::AsciiMetadataKey>,
+}
+
+#[cfg(feature = "tonic")]
+impl FacadeInterceptor {
+	/// Creates an interceptor without any metadata key.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the key is weak, see [`UuidV47Key::validate`].
+	pub fn new(key: UuidV47Key) -> Result<Self, WeakKeyError> {
+		key.validate()?;
+		Ok(Self::new_allow_weak_key(key))
+	}
+
+	/// Same as [`FacadeInterceptor::new`], but accepts weak keys.
+	#[must_use]
+	pub fn new_allow_weak_key(key: UuidV47Key) -> Self {
+		Self {
+			key,
+			metadata_keys: Vec::new(),
+		}
+	}
+
+	/// Decode the facade in the metadata entry `name`, if present.
+	///
+	/// # Panics
+	///
+	/// Panics if `name` is not a valid lowercase ASCII metadata key.
+	#[must_use]
+	pub fn with_metadata_key(mut self, name: &'static str) -> Self {
+		self.metadata_keys
+			.push(tonic::metadata::AsciiMetadataKey::from_static(name));
+		self
+	}
+}
+
+#[cfg(feature = "tonic")]
+impl tonic::service::Interceptor for FacadeInterceptor {
+	fn call(
+		&mut self,
+		mut request: tonic::Request<()>,
+	) -> Result<tonic::Request<()>, tonic::Status> {
+		for name in &self.metadata_keys {
+			let Some(value) = request.metadata().get(name) else {
+				continue;
+			};
+
+			let facade: crate::uuid::Uuid128 = value
+				.to_str()
+				.map_err(|_| UuidParseError::InvalidHex)?
+				.parse()?;
+			let v7 = facade
+				.checked_decode_from_v4facade(&self.key, SentinelPolicy::Reject)
+				.map_err(|_| tonic::Status::not_found("Not found"))?;
+
+			let value = v7
+				.to_string()
+				.parse()
+				.map_err(|_| tonic::Status::internal("invalid metadata value"))?;
+			request.metadata_mut().insert(name.clone(), value);
+		}
+
+		Ok(request)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_proto_roundtrip() {
+		use prost::Message;
+
+		let uuid: crate::uuid::Uuid128 = "00000000-0000-7000-8000-000000000000".parse().unwrap();
+		let encoded = Uuid128::from(uuid).encode_to_vec();
+		let decoded = Uuid128::decode(encoded.as_slice()).unwrap();
+		assert_eq!(crate::uuid::Uuid128::try_from(decoded).unwrap(), uuid);
+
+		// 15 bytes
+		let short = Uuid128 { value: vec![0; 15] };
+		assert!(matches!(
+			crate::uuid::Uuid128::try_from(short),
+			Err(UuidValidationError::InvalidLength)
+		));
+
+		// version 1
+		let mut value = uuid.as_bytes().to_vec();
+		value[6] = 0x10;
+		assert!(matches!(
+			crate::uuid::Uuid128::try_from(Uuid128 { value }),
+			Err(UuidValidationError::InvalidVersion)
+		));
+	}
+
+	#[cfg(feature = "tonic")]
+	#[test]
+	fn test_facade_interceptor() {
+		use tonic::service::Interceptor;
+		use tonic::Code;
+
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		assert_eq!(
+			FacadeInterceptor::new(key).unwrap_err(),
+			WeakKeyError::ExampleKey
+		);
+		let mut interceptor =
+			FacadeInterceptor::new_allow_weak_key(key).with_metadata_key("x-resource-id");
+
+		let call = |interceptor: &mut FacadeInterceptor, value: &'static str| {
+			let mut request = tonic::Request::new(());
+			request
+				.metadata_mut()
+				.insert("x-resource-id", value.parse().unwrap());
+			interceptor.call(request)
+		};
+
+		let request = call(&mut interceptor, "22d97126-9609-4000-8000-000000000000").unwrap();
+		assert_eq!(
+			request.metadata().get("x-resource-id").unwrap(),
+			"00000000-0000-7000-8000-000000000000"
+		);
+
+		let err = call(&mut interceptor, "not-a-uuid").unwrap_err();
+		assert_eq!(err.code(), Code::InvalidArgument);
+
+		let err = call(&mut interceptor, "00000000-0000-7000-8000-000000000000").unwrap_err();
+		assert_eq!(err.code(), Code::NotFound);
+
+		// absent metadata is ignored
+		assert!(interceptor.call(tonic::Request::new(())).is_ok());
+
+		let facade = Uuid128::from(
+			"22d97126-9609-4000-8000-000000000000"
+				.parse::<crate::uuid::Uuid128>()
+				.unwrap(),
+		);
+		assert_eq!(
+			decode_facade(&facade, &key).unwrap().to_string(),
+			"00000000-0000-7000-8000-000000000000"
+		);
+	}
+}
@@ -0,0 +1,293 @@
+//! Python bindings (PyO3).
+//!
+//! Build the `uuid47` extension module with [maturin](https://www.maturin.rs) from the repository root:
+//!
+//! ```shell
+//! maturin develop --release
+//! python -m unittest discover python/tests
+//! ```
+//!
+//! Every function taking a UUID accepts `Uuid128`, `uuid.UUID`, `str` or 16 `bytes`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::pybacked::PyBackedStr;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyBytes, PyDict, PyType};
+
+use crate::error::{UuidParseError, UuidValidationError, WeakKeyError};
+use crate::key::UuidV47Key;
+use crate::sentinel::SentinelPolicy;
+use crate::uuid::Uuid128;
+
+impl From<UuidParseError> for PyErr {
+	fn from(err: UuidParseError) -> Self {
+		PyValueError::new_err(err.to_string())
+	}
+}
+
+impl From<UuidValidationError> for PyErr {
+	fn from(err: UuidValidationError) -> Self {
+		PyValueError::new_err(err.to_string())
+	}
+}
+
+impl From<WeakKeyError> for PyErr {
+	fn from(err: WeakKeyError) -> Self {
+		PyValueError::new_err(err.to_string())
+	}
+}
+
+/// `uuid.UUID` type, imported once.
+static UUID_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+/// A 128-bit UUID (`UUIDv4` or `UUIDv7`).
+#[pyclass(
+	name = "Uuid128",
+	module = "uuid47",
+	frozen,
+	eq,
+	ord,
+	hash,
+	skip_from_py_object
+)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyUuid128(Uuid128);
+
+#[pymethods]
+impl PyUuid128 {
+	/// Creates a UUID from `Uuid128`, `uuid.UUID`, `str` or 16 `bytes`.
+	#[new]
+	fn py_new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+		extract_uuid(value).map(Self)
+	}
+
+	/// Get this UUID version.
+	#[getter]
+	fn version(&self) -> u8 {
+		self.0.uuid_version()
+	}
+
+	/// Get the raw 16 bytes.
+	#[getter]
+	fn bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+		PyBytes::new(py, self.0.as_bytes())
+	}
+
+	/// Get the 48-bit Unix timestamp in milliseconds of a `UUIDv7`.
+	#[getter]
+	fn timestamp_ms(&self) -> u64 {
+		self.0.timestamp_ms()
+	}
+
+	/// Convert to `uuid.UUID`.
+	fn to_uuid<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+		to_py_uuid(py, &self.0)
+	}
+
+	/// Encode this `UUIDv7` into `UUIDv4` facade.
+	fn encode_as_v4facade(&self, key: &PyUuidV47Key) -> PyResult<Self> {
+		encode(&self.0, key).map(Self)
+	}
+
+	/// Decode this `UUIDv4` facade back into `UUIDv7`.
+	fn decode_from_v4facade(&self, key: &PyUuidV47Key) -> PyResult<Self> {
+		decode(&self.0, key).map(Self)
+	}
+
+	fn __str__(&self) -> String {
+		self.0.to_string()
+	}
+
+	fn __repr__(&self) -> String {
+		format!("Uuid128('{}')", self.0)
+	}
+}
+
+/// Key for `UUIDv47` encoding/decoding.
+#[pyclass(
+	name = "UuidV47Key",
+	module = "uuid47",
+	frozen,
+	eq,
+	skip_from_py_object
+)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct PyUuidV47Key(UuidV47Key);
+
+#[pymethods]
+impl PyUuidV47Key {
+	/// Creates a new key from two 64-bit halves.
+	///
+	/// Raises `ValueError` for weak keys (see `UuidV47Key::validate`) unless `allow_weak_key` is true.
+	#[new]
+	#[pyo3(signature = (k0, k1, *, allow_weak_key = false))]
+	fn py_new(k0: u64, k1: u64, allow_weak_key: bool) -> PyResult<Self> {
+		let key = UuidV47Key::new(k0, k1);
+		if !allow_weak_key {
+			key.validate()?;
+		}
+
+		Ok(Self(key))
+	}
+
+	/// First 64 bits of the key.
+	#[getter]
+	fn k0(&self) -> u64 {
+		self.0.k0
+	}
+
+	/// Second 64 bits of the key.
+	#[getter]
+	fn k1(&self) -> u64 {
+		self.0.k1
+	}
+
+	fn __repr__(&self) -> &'static str {
+		// never print the secret
+		"UuidV47Key(...)"
+	}
+}
+
+/// Extract a `Uuid128` from `Uuid128`, `uuid.UUID`, `str` or 16 `bytes`.
+fn extract_uuid(value: &Bound<'_, PyAny>) -> PyResult<Uuid128> {
+	if let Ok(uuid) = value.cast::<PyUuid128>() {
+		return Ok(uuid.get().0);
+	}
+
+	if let Ok(bytes) = value.cast::<PyBytes>() {
+		return Ok(Uuid128::try_from(bytes.as_bytes())?);
+	}
+
+	if let Ok(s) = value.extract::<PyBackedStr>() {
+		return Ok(s.parse()?);
+	}
+
+	let uuid_type = UUID_TYPE.import(value.py(), "uuid", "UUID")?;
+	if value.is_instance(uuid_type)? {
+		let bytes = value.getattr("bytes")?;
+		return Ok(Uuid128::try_from(bytes.cast::<PyBytes>()?.as_bytes())?);
+	}
+
+	Err(PyValueError::new_err(
+		"expected Uuid128, uuid.UUID, str or 16 bytes",
+	))
+}
+
+/// Convert a `Uuid128` into `uuid.UUID`.
+fn to_py_uuid<'py>(py: Python<'py>, uuid: &Uuid128) -> PyResult<Bound<'py, PyAny>> {
+	let kwargs = PyDict::new(py);
+	kwargs.set_item("bytes", PyBytes::new(py, uuid.as_bytes()))?;
+
+	UUID_TYPE
+		.import(py, "uuid", "UUID")?
+		.call((), Some(&kwargs))
+}
+
+fn encode(uuid: &Uuid128, key: &PyUuidV47Key) -> PyResult<Uuid128> {
+	Ok(uuid.checked_encode_as_v4facade(&key.0, SentinelPolicy::Passthrough)?)
+}
+
+fn decode(uuid: &Uuid128, key: &PyUuidV47Key) -> PyResult<Uuid128> {
+	Ok(uuid.checked_decode_from_v4facade(&key.0, SentinelPolicy::Passthrough)?)
+}
+
+/// Apply `f` to a buffer of concatenated 16-byte UUIDs.
+fn map_bytes<'py>(
+	py: Python<'py>,
+	data: &[u8],
+	f: impl Fn(&Uuid128) -> PyResult<Uuid128>,
+) -> PyResult<Bound<'py, PyBytes>> {
+	if !data.len().is_multiple_of(16) {
+		return Err(PyValueError::new_err(
+			"buffer length must be a multiple of 16",
+		));
+	}
+
+	let mut out = Vec::with_capacity(data.len());
+	for chunk in data.chunks_exact(16) {
+		out.extend_from_slice(f(&Uuid128::try_from(chunk)?)?.as_bytes());
+	}
+
+	Ok(PyBytes::new(py, &out))
+}
+
+/// Parse a UUID string.
+#[pyfunction]
+fn parse(s: &str) -> PyResult<PyUuid128> {
+	Ok(PyUuid128(s.parse()?))
+}
+
+/// Encode a `UUIDv7` into `UUIDv4` facade.
+#[pyfunction]
+fn encode_as_v4facade(uuid: &Bound<'_, PyAny>, key: &PyUuidV47Key) -> PyResult<PyUuid128> {
+	encode(&extract_uuid(uuid)?, key).map(PyUuid128)
+}
+
+/// Decode a `UUIDv4` facade back into `UUIDv7`.
+#[pyfunction]
+fn decode_from_v4facade(uuid: &Bound<'_, PyAny>, key: &PyUuidV47Key) -> PyResult<PyUuid128> {
+	decode(&extract_uuid(uuid)?, key).map(PyUuid128)
+}
+
+/// Encode a sequence of `UUIDv7` into `uuid.UUID` facades.
+#[pyfunction]
+fn encode_many<'py>(
+	py: Python<'py>,
+	uuids: Vec<Bound<'py, PyAny>>,
+	key: &PyUuidV47Key,
+) -> PyResult<Vec<Bound<'py, PyAny>>> {
+	uuids
+		.iter()
+		.map(|uuid| to_py_uuid(py, &encode(&extract_uuid(uuid)?, key)?))
+		.collect()
+}
+
+/// Decode a sequence of `UUIDv4` facades into `uuid.UUID`.
+#[pyfunction]
+fn decode_many<'py>(
+	py: Python<'py>,
+	uuids: Vec<Bound<'py, PyAny>>,
+	key: &PyUuidV47Key,
+) -> PyResult<Vec<Bound<'py, PyAny>>> {
+	uuids
+		.iter()
+		.map(|uuid| to_py_uuid(py, &decode(&extract_uuid(uuid)?, key)?))
+		.collect()
+}
+
+/// Encode concatenated 16-byte `UUIDv7` (e.g. `numpy_array.tobytes()`) into facades.
+#[pyfunction]
+fn encode_bytes<'py>(
+	py: Python<'py>,
+	data: &[u8],
+	key: &PyUuidV47Key,
+) -> PyResult<Bound<'py, PyBytes>> {
+	map_bytes(py, data, |uuid| encode(uuid, key))
+}
+
+/// Decode concatenated 16-byte `UUIDv4` facades (e.g. `numpy_array.tobytes()`).
+#[pyfunction]
+fn decode_bytes<'py>(
+	py: Python<'py>,
+	data: &[u8],
+	key: &PyUuidV47Key,
+) -> PyResult<Bound<'py, PyBytes>> {
+	map_bytes(py, data, |uuid| decode(uuid, key))
+}
+
+/// Python module `uuid47`.
+#[pymodule]
+fn uuid47(module: &Bound<'_, PyModule>) -> PyResult<()> {
+	module.add_class::<PyUuid128>()?;
+	module.add_class::<PyUuidV47Key>()?;
+	module.add_function(wrap_pyfunction!(parse, module)?)?;
+	module.add_function(wrap_pyfunction!(encode_as_v4facade, module)?)?;
+	module.add_function(wrap_pyfunction!(decode_from_v4facade, module)?)?;
+	module.add_function(wrap_pyfunction!(encode_many, module)?)?;
+	module.add_function(wrap_pyfunction!(decode_many, module)?)?;
+	module.add_function(wrap_pyfunction!(encode_bytes, module)?)?;
+	module.add_function(wrap_pyfunction!(decode_bytes, module)?)?;
+
+	Ok(())
+}
@@ -0,0 +1,78 @@
+use crate::uuid::{Uuid128, MAX_TIMESTAMP_MS};
+
+/// Inclusive bounds of `UUIDv7` for a time window.
+///
+/// Use with `WHERE id BETWEEN start AND end` to scan all `UUIDv7` created in the window.
+///
+/// # Examples
+/// ```
+/// use uuid47::UuidV7Range;
+///
+/// let range = UuidV7Range::from_time_range(1_000, 2_000);
+/// assert_eq!(range.start().to_string(), "00000000-03e8-7000-8000-000000000000");
+/// assert_eq!(range.end().to_string(), "00000000-07d0-7fff-bfff-ffffffffffff");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct UuidV7Range {
+	start: Uuid128,
+	end: Uuid128,
+}
+
+impl UuidV7Range {
+	/// Creates the bounds covering every millisecond from `start_ms` to `end_ms`, both inclusive.
+	///
+	/// Timestamps above 48 bits are clamped to the largest `UUIDv7` timestamp.<br>
+	/// If `start_ms` is greater than `end_ms`, they are swapped.
+	#[must_use]
+	pub fn from_time_range(start_ms: u64, end_ms: u64) -> Self {
+		// clamp before ordering, truncating could invert the bounds
+		let (start_ms, end_ms) = (start_ms.min(MAX_TIMESTAMP_MS), end_ms.min(MAX_TIMESTAMP_MS));
+		let (start_ms, end_ms) = if start_ms <= end_ms {
+			(start_ms, end_ms)
+		} else {
+			(end_ms, start_ms)
+		};
+
+		Self {
+			start: Uuid128::min_for_timestamp(start_ms),
+			end: Uuid128::max_for_timestamp(end_ms),
+		}
+	}
+
+	/// Get the inclusive lower bound.
+	#[must_use]
+	pub fn start(&self) -> Uuid128 {
+		self.start
+	}
+
+	/// Get the inclusive upper bound.
+	#[must_use]
+	pub fn end(&self) -> Uuid128 {
+		self.end
+	}
+
+	/// Check whether the timestamp of `uuid` falls in this range.
+	#[must_use]
+	pub fn contains(&self, uuid: &Uuid128) -> bool {
+		let ts = uuid.timestamp_ms();
+		self.start.timestamp_ms() <= ts && ts <= self.end.timestamp_ms()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_out_of_range_bounds() {
+		// truncated, 2^48 + 5 would become 5 and sort before 10
+		let range = UuidV7Range::from_time_range(10, (1 << 48) + 5);
+		assert_eq!(range.start().timestamp_ms(), 10);
+		assert_eq!(range.end().timestamp_ms(), MAX_TIMESTAMP_MS);
+		assert!(range.contains(&Uuid128::min_for_timestamp(1 << 40)));
+
+		let range = UuidV7Range::from_time_range(u64::MAX, 1 << 48);
+		assert_eq!(range.start().timestamp_ms(), MAX_TIMESTAMP_MS);
+		assert_eq!(range.end().timestamp_ms(), MAX_TIMESTAMP_MS);
+	}
+}
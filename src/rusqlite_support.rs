@@ -0,0 +1,27 @@
+//! [`rusqlite`] integration: `Uuid128` as SQLite `BLOB`.
+//!
+//! Values are validated with [`Uuid128::from_bytes`](crate::Uuid128::from_bytes).
+
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::uuid::Uuid128;
+
+impl ToSql for Uuid128 {
+	fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+		Ok(ToSqlOutput::from(self.as_bytes().as_slice()))
+	}
+}
+
+impl FromSql for Uuid128 {
+	fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+		let bytes = value.as_blob()?;
+		if bytes.len() != 16 {
+			return Err(FromSqlError::InvalidBlobSize {
+				expected_size: 16,
+				blob_size: bytes.len(),
+			});
+		}
+
+		Uuid128::try_from(bytes).map_err(|err| FromSqlError::Other(Box::new(err)))
+	}
+}
@@ -0,0 +1,19 @@
+/// Facade transform scheme.
+///
+/// Each scheme produces a valid `UUIDv4` facade, but facades of different schemes are not interchangeable.<br>
+/// A facade must be decoded with the same scheme and key it was encoded with.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FacadeScheme {
+	/// The reference `UUIDv47` transform, compatible with other implementations.
+	///
+	/// Only the 48-bit timestamp is masked, the 74 random bits appear verbatim in the facade.
+	#[default]
+	Reference,
+
+	/// Full-width transform, version 1.
+	///
+	/// All 122 bits excluding version and variant are passed through a keyed Feistel permutation
+	/// (8 rounds, SipHash-2-4 round function), so the facade shares no visible bits with the `UUIDv7`.<br>
+	/// Not compatible with other `UUIDv47` implementations.
+	FullWidthV1,
+}
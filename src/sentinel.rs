@@ -0,0 +1,13 @@
+/// How the Nil and Max UUID are handled by the checked facade transforms.
+///
+/// See [`Uuid128::checked_encode_as_v4facade`](crate::Uuid128::checked_encode_as_v4facade)
+/// and [`Uuid128::checked_decode_from_v4facade`](crate::Uuid128::checked_decode_from_v4facade).
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SentinelPolicy {
+	/// Return the Nil and Max UUID unchanged.
+	#[default]
+	Passthrough,
+
+	/// Fail with [`UuidValidationError::Sentinel`](crate::UuidValidationError::Sentinel).
+	Reject,
+}
@@ -0,0 +1,296 @@
+use crate::key::UuidV47Key;
+
+/// One SipRound.
+#[inline(always)]
+fn sipround(v: &mut [u64; 4]) {
+	v[0] = v[0].wrapping_add(v[1]);
+	v[2] = v[2].wrapping_add(v[3]);
+	v[1] = v[1].rotate_left(13);
+	v[3] = v[3].rotate_left(16);
+	v[1] ^= v[0];
+	v[3] ^= v[2];
+	v[0] = v[0].rotate_left(32);
+	v[2] = v[2].wrapping_add(v[1]);
+	v[0] = v[0].wrapping_add(v[3]);
+	v[1] = v[1].rotate_left(17);
+	v[3] = v[3].rotate_left(21);
+	v[1] ^= v[2];
+	v[3] ^= v[0];
+	v[2] = v[2].rotate_left(32);
+}
+
+/// Initialize the internal state from the key.
+#[inline(always)]
+fn init(k0: u64, k1: u64) -> [u64; 4] {
+	[
+		0x736f_6d65_7073_6575_u64 ^ k0,
+		0x646f_7261_6e64_6f6d_u64 ^ k1,
+		0x6c79_6765_6e65_7261_u64 ^ k0,
+		0x7465_6462_7974_6573_u64 ^ k1,
+	]
+}
+
+/// Absorb one 64-bit message word (`C` compression rounds).
+#[inline(always)]
+fn compress_rounds<const C: usize>(v: &mut [u64; 4], m: u64) {
+	v[3] ^= m;
+	for _ in 0..C {
+		sipround(v);
+	}
+	v[0] ^= m;
+}
+
+/// Absorb one 64-bit message word (2 compression rounds).
+#[inline(always)]
+fn compress(v: &mut [u64; 4], m: u64) {
+	compress_rounds::<2>(v, m);
+}
+
+/// Absorb the last block and run `D` finalization rounds.
+#[inline(always)]
+fn finalize_rounds<const C: usize, const D: usize>(mut v: [u64; 4], b: u64) -> u64 {
+	compress_rounds::<C>(&mut v, b);
+
+	v[2] ^= 0xff;
+	for _ in 0..D {
+		sipround(&mut v);
+	}
+
+	v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+/// Absorb the last block and run 4 finalization rounds.
+#[inline(always)]
+fn finalize(v: [u64; 4], b: u64) -> u64 {
+	finalize_rounds::<2, 4>(v, b)
+}
+
+/// SipHash-2-4 (reference) in Rust
+#[inline(always)]
+pub(crate) fn siphash24(input: &[u8], k0: u64, k1: u64) -> u64 {
+	siphash::<2, 4>(input, k0, k1)
+}
+
+/// SipHash-1-3, the reduced-round variant used by the Rust standard library.
+#[cfg(feature = "prf-siphash13")]
+#[inline(always)]
+pub(crate) fn siphash13(input: &[u8], k0: u64, k1: u64) -> u64 {
+	siphash::<1, 3>(input, k0, k1)
+}
+
+/// SipHash-C-D.
+#[inline(always)]
+fn siphash<const C: usize, const D: usize>(input: &[u8], k0: u64, k1: u64) -> u64 {
+	let mut v = init(k0, k1);
+
+	let mut b = (input.len() as u64) << 56;
+
+	let mut chunks = input.chunks_exact(8);
+	for chunk in chunks.by_ref() {
+		compress_rounds::<C>(&mut v, u64::from_le_bytes(chunk.try_into().unwrap()));
+	}
+
+	// last 0..7 bytes
+	let rem = chunks.remainder();
+	let mut t = 0u64;
+	for (i, &byte) in rem.iter().enumerate() {
+		t |= (byte as u64) << (8 * i as u32);
+	}
+	b |= t;
+
+	finalize_rounds::<C, D>(v, b)
+}
+
+/// Streaming SipHash-2-4 hasher.
+///
+/// This is the same keyed PRF used for the `UUIDv47` transform, usable for related keyed tokens.<br>
+/// Output is the SipHash-2-4 of all bytes passed to [`write`](core::hash::Hasher::write),
+/// regardless of how they are split across calls.
+///
+/// Note that the integer methods of [`Hasher`](core::hash::Hasher) write native-endian bytes.
+/// Use `write` with explicit `to_le_bytes`/`to_be_bytes` for portable results.
+///
+/// # Examples
+/// ```
+/// use core::hash::Hasher;
+/// use uuid47::{SipHasher24, UuidV47Key};
+///
+/// let key = UuidV47Key::new(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+/// let mut hasher = SipHasher24::new_with_key(&key);
+/// hasher.write(&[0, 1, 2, 3, 4, 5, 6]);
+/// hasher.write(&[7, 8, 9, 10, 11, 12, 13, 14]);
+///
+/// assert_eq!(hasher.finish(), 0xa129ca6149be45e5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SipHasher24 {
+	state: [u64; 4],
+	tail: u64,
+	ntail: usize,
+	length: usize,
+}
+
+impl SipHasher24 {
+	/// Creates a new hasher with the two 64-bit key halves.
+	#[must_use]
+	pub fn new_with_keys(k0: u64, k1: u64) -> Self {
+		Self {
+			state: init(k0, k1),
+			tail: 0,
+			ntail: 0,
+			length: 0,
+		}
+	}
+
+	/// Creates a new hasher keyed with a `UuidV47Key`.
+	#[must_use]
+	pub fn new_with_key(key: &UuidV47Key) -> Self {
+		Self::new_with_keys(key.k0, key.k1)
+	}
+}
+
+impl core::hash::Hasher for SipHasher24 {
+	fn write(&mut self, bytes: &[u8]) {
+		self.length += bytes.len();
+
+		let mut bytes = bytes;
+
+		// fill the pending tail first
+		while self.ntail != 0 && !bytes.is_empty() {
+			self.tail |= u64::from(bytes[0]) << (8 * self.ntail);
+			self.ntail += 1;
+			bytes = &bytes[1..];
+
+			if self.ntail == 8 {
+				compress(&mut self.state, self.tail);
+				self.tail = 0;
+				self.ntail = 0;
+			}
+		}
+
+		let mut chunks = bytes.chunks_exact(8);
+		for chunk in chunks.by_ref() {
+			compress(
+				&mut self.state,
+				u64::from_le_bytes(chunk.try_into().unwrap()),
+			);
+		}
+
+		for &byte in chunks.remainder() {
+			self.tail |= u64::from(byte) << (8 * self.ntail);
+			self.ntail += 1;
+		}
+	}
+
+	fn finish(&self) -> u64 {
+		finalize(self.state, ((self.length as u64) << 56) | self.tail)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::hash::Hasher;
+
+	/// Reference vectors from the SipHash paper (key = 00..0f, message = 00..(n-1)).
+	const VECTORS: [u64; 64] = [
+		0x726fdb47dd0e0e31,
+		0x74f839c593dc67fd,
+		0x0d6c8009d9a94f5a,
+		0x85676696d7fb7e2d,
+		0xcf2794e0277187b7,
+		0x18765564cd99a68d,
+		0xcbc9466e58fee3ce,
+		0xab0200f58b01d137,
+		0x93f5f5799a932462,
+		0x9e0082df0ba9e4b0,
+		0x7a5dbbc594ddb9f3,
+		0xf4b32f46226bada7,
+		0x751e8fbc860ee5fb,
+		0x14ea5627c0843d90,
+		0xf723ca908e7af2ee,
+		0xa129ca6149be45e5,
+		0x3f2acc7f57c29bdb,
+		0x699ae9f52cbe4794,
+		0x4bc1b3f0968dd39c,
+		0xbb6dc91da77961bd,
+		0xbed65cf21aa2ee98,
+		0xd0f2cbb02e3b67c7,
+		0x93536795e3a33e88,
+		0xa80c038ccd5ccec8,
+		0xb8ad50c6f649af94,
+		0xbce192de8a85b8ea,
+		0x17d835b85bbb15f3,
+		0x2f2e6163076bcfad,
+		0xde4daaaca71dc9a5,
+		0xa6a2506687956571,
+		0xad87a3535c49ef28,
+		0x32d892fad841c342,
+		0x7127512f72f27cce,
+		0xa7f32346f95978e3,
+		0x12e0b01abb051238,
+		0x15e034d40fa197ae,
+		0x314dffbe0815a3b4,
+		0x027990f029623981,
+		0xcadcd4e59ef40c4d,
+		0x9abfd8766a33735c,
+		0x0e3ea96b5304a7d0,
+		0xad0c42d6fc585992,
+		0x187306c89bc215a9,
+		0xd4a60abcf3792b95,
+		0xf935451de4f21df2,
+		0xa9538f0419755787,
+		0xdb9acddff56ca510,
+		0xd06c98cd5c0975eb,
+		0xe612a3cb9ecba951,
+		0xc766e62cfcadaf96,
+		0xee64435a9752fe72,
+		0xa192d576b245165a,
+		0x0a8787bf8ecb74b2,
+		0x81b3e73d20b49b6f,
+		0x7fa8220ba3b2ecea,
+		0x245731c13ca42499,
+		0xb78dbfaf3a8d83bd,
+		0xea1ad565322a1a0b,
+		0x60e61c23a3795013,
+		0x6606d7e446282b93,
+		0x6ca4ecb15c5f91e1,
+		0x9f626da15c9625f3,
+		0xe51b38608ef25f57,
+		0x958a324ceb064572,
+	];
+
+	const K0: u64 = 0x0706_0504_0302_0100;
+	const K1: u64 = 0x0f0e_0d0c_0b0a_0908;
+
+	#[test]
+	fn test_siphash24_vectors() {
+		let input: Vec<u8> = (0..64).collect();
+
+		for (len, &expected) in VECTORS.iter().enumerate() {
+			assert_eq!(siphash24(&input[..len], K0, K1), expected, "length {len}");
+		}
+	}
+
+	#[test]
+	fn test_hasher_streaming() {
+		let input: Vec<u8> = (0..64).collect();
+
+		for (len, &expected) in VECTORS.iter().enumerate() {
+			// split the message at every position
+			for split in 0..=len {
+				let mut hasher = SipHasher24::new_with_keys(K0, K1);
+				hasher.write(&input[..split]);
+				hasher.write(&input[split..len]);
+				assert_eq!(hasher.finish(), expected, "length {len}, split {split}");
+			}
+
+			// byte by byte
+			let mut hasher = SipHasher24::new_with_keys(K0, K1);
+			for &byte in &input[..len] {
+				hasher.write(&[byte]);
+			}
+			assert_eq!(hasher.finish(), expected, "length {len}, bytewise");
+		}
+	}
+}
@@ -0,0 +1,86 @@
+//! [`sqlx`] integration: `Uuid128` as Postgres `uuid`, MySQL `BINARY(16)` and SQLite `BLOB`.
+//!
+//! Binary values are validated with [`Uuid128::from_bytes`](crate::Uuid128::from_bytes).
+
+#[cfg(any(feature = "sqlx-mysql", feature = "sqlx-sqlite"))]
+mod binary {
+	use sqlx::encode::IsNull;
+	use sqlx::error::BoxDynError;
+	use sqlx::{Database, Decode, Encode, Type};
+
+	use crate::uuid::Uuid128;
+
+	/// Implement `Type`, `Encode` and `Decode` for a database storing `Uuid128` as 16-byte binary.
+	macro_rules! impl_sqlx_binary {
+		($db:ty) => {
+			impl Type<$db> for Uuid128 {
+				fn type_info() -> <$db as Database>::TypeInfo {
+					<Vec<u8> as Type<$db>>::type_info()
+				}
+
+				fn compatible(ty: &<$db as Database>::TypeInfo) -> bool {
+					<Vec<u8> as Type<$db>>::compatible(ty)
+				}
+			}
+
+			impl<'q> Encode<'q, $db> for Uuid128 {
+				fn encode_by_ref(
+					&self,
+					buf: &mut <$db as Database>::ArgumentBuffer<'q>,
+				) -> Result<IsNull, BoxDynError> {
+					<Vec<u8> as Encode<'q, $db>>::encode(self.as_bytes().to_vec(), buf)
+				}
+			}
+
+			impl<'r> Decode<'r, $db> for Uuid128 {
+				fn decode(value: <$db as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+					let bytes = <&[u8] as Decode<'r, $db>>::decode(value)?;
+					Ok(Uuid128::try_from(bytes)?)
+				}
+			}
+		};
+	}
+
+	#[cfg(feature = "sqlx-mysql")]
+	impl_sqlx_binary!(sqlx::MySql);
+
+	#[cfg(feature = "sqlx-sqlite")]
+	impl_sqlx_binary!(sqlx::Sqlite);
+}
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres {
+	use sqlx::encode::IsNull;
+	use sqlx::error::BoxDynError;
+	use sqlx::postgres::types::Oid;
+	use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef};
+	use sqlx::{Decode, Encode, Postgres, Type};
+
+	use crate::uuid::Uuid128;
+
+	/// OID of the built-in `uuid` type.
+	const UUID_OID: Oid = Oid(2950);
+
+	impl Type<Postgres> for Uuid128 {
+		fn type_info() -> PgTypeInfo {
+			PgTypeInfo::with_oid(UUID_OID)
+		}
+	}
+
+	impl Encode<'_, Postgres> for Uuid128 {
+		fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+			buf.extend_from_slice(self.as_bytes());
+
+			Ok(IsNull::No)
+		}
+	}
+
+	impl Decode<'_, Postgres> for Uuid128 {
+		fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+			match value.format() {
+				PgValueFormat::Binary => Ok(Uuid128::try_from(value.as_bytes()?)?),
+				PgValueFormat::Text => Ok(value.as_str()?.parse()?),
+			}
+		}
+	}
+}
@@ -0,0 +1,596 @@
+//! Tower middleware rewriting UUID in JSON bodies.
+//!
+//! A safety net for handlers that still serialize internal `UUIDv7` directly:
+//! responses get their `UUIDv7` replaced with `UUIDv4` facades, requests get their facades decoded back.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use http::{HeaderMap, HeaderValue, Request, Response, StatusCode};
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use serde::de::IgnoredAny;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::error::WeakKeyError;
+use crate::key::UuidV47Key;
+use crate::uuid::Uuid128;
+
+/// Default of [`FacadeJsonLayer::with_body_limit`], 2 MiB.
+const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Body passed to the inner service and returned by [`FacadeJson`].
+///
+/// Either a rewritten JSON body, or the original body (with the bytes already read in front of it) passed through untouched.
+#[derive(Debug)]
+pub struct FacadeJsonBody<B> {
+	buffered: Option<Bytes>,
+	inner: Option<Pin<Box<B>>>,
+}
+
+impl<B> FacadeJsonBody<B> {
+	fn full(bytes: Bytes) -> Self {
+		Self {
+			buffered: Some(bytes),
+			inner: None,
+		}
+	}
+
+	fn passthrough(buffered: Option<Bytes>, inner: Pin<Box<B>>) -> Self {
+		Self {
+			buffered,
+			inner: Some(inner),
+		}
+	}
+}
+
+impl<B: Body<Data = Bytes>> Body for FacadeJsonBody<B> {
+	type Data = Bytes;
+	type Error = B::Error;
+
+	fn poll_frame(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+		let this = self.get_mut();
+		if let Some(bytes) = this.buffered.take() {
+			return Poll::Ready(Some(Ok(Frame::data(bytes))));
+		}
+
+		match &mut this.inner {
+			Some(inner) => inner.as_mut().poll_frame(cx),
+			None => Poll::Ready(None),
+		}
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.buffered.is_none()
+			&& self
+				.inner
+				.as_ref()
+				.is_none_or(|inner| inner.is_end_stream())
+	}
+
+	fn size_hint(&self) -> SizeHint {
+		let buffered = self.buffered.as_ref().map_or(0, |bytes| bytes.len() as u64);
+		let Some(inner) = &self.inner else {
+			return SizeHint::with_exact(buffered);
+		};
+
+		let inner = inner.size_hint();
+		let mut hint = SizeHint::new();
+		hint.set_lower(inner.lower() + buffered);
+		if let Some(upper) = inner.upper() {
+			hint.set_upper(upper + buffered);
+		}
+		hint
+	}
+}
+
+/// [`Layer`] applying [`FacadeJson`].
+///
+/// Only `application/json` (and `+json`) bodies up to the body limit are rewritten, any other body is passed through.
+///
+/// * Responses: every `UUIDv7` string is encoded into `UUIDv4` facade.
+/// * Requests: `UUIDv4` strings at the paths given to [`FacadeJsonLayer::with_paths`] are decoded back into `UUIDv7`.<br>
+///   Without paths requests are left untouched, as genuine random `UUIDv4` cannot be told apart from facades.
+///
+/// Only the bytes of the rewritten strings change, so member order, numbers and whitespace are preserved.
+///
+/// # Examples
+/// ```
+/// use uuid47::{FacadeJsonLayer, UuidV47Key};
+///
+/// let key = UuidV47Key::new(0x5f1c9a0e7d3b2846, 0xb4e8126fa0c37d95);
+/// let layer = FacadeJsonLayer::new(key)?.with_paths(["/id", "/items/*/owner_id"]);
+/// # Ok::<(), uuid47::WeakKeyError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FacadeJsonLayer {
+	config: Config,
+}
+
+#[derive(Debug, Clone)]
+struct Config {
+	key: UuidV47Key,
+	paths: Option<Vec<Vec<String>>>,
+	body_limit: usize,
+}
+
+impl FacadeJsonLayer {
+	/// Creates a layer rewriting every `UUIDv7` string in JSON responses.
+	///
+	/// # Errors
+	///
+	/// Returns an error if the key is weak, see [`UuidV47Key::validate`].
+	pub fn new(key: UuidV47Key) -> Result<Self, WeakKeyError> {
+		key.validate()?;
+		Ok(Self::new_allow_weak_key(key))
+	}
+
+	/// Same as [`FacadeJsonLayer::new`], but accepts weak keys.
+	#[must_use]
+	pub fn new_allow_weak_key(key: UuidV47Key) -> Self {
+		Self {
+			config: Config {
+				key,
+				paths: None,
+				body_limit: DEFAULT_BODY_LIMIT,
+			},
+		}
+	}
+
+	/// Only rewrite the values at the given JSON Pointers (RFC 6901), in responses and requests.
+	///
+	/// A `*` segment matches any object member or array element.
+	#[must_use]
+	pub fn with_paths<I, P>(mut self, paths: I) -> Self
+	where
+		I: IntoIterator<Item = P>,
+		P: AsRef<str>,
+	{
+		let paths = paths
+			.into_iter()
+			.map(|path| {
+				path.as_ref()
+					.split('/')
+					.skip(1)
+					.map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+					.collect()
+			})
+			.collect();
+
+		self.config.paths = Some(paths);
+		self
+	}
+
+	/// Largest body buffered for rewriting in bytes (2 MiB by default), larger bodies are passed through untouched.
+	#[must_use]
+	pub fn with_body_limit(mut self, limit: usize) -> Self {
+		self.config.body_limit = limit;
+		self
+	}
+}
+
+impl<S> Layer<S> for FacadeJsonLayer {
+	type Service = FacadeJson<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		FacadeJson {
+			inner,
+			config: self.config.clone(),
+		}
+	}
+}
+
+/// Middleware rewriting UUID in JSON bodies, see [`FacadeJsonLayer`].
+#[derive(Debug, Clone)]
+pub struct FacadeJson<S> {
+	inner: S,
+	config: Config,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for FacadeJson<S>
+where
+	S: Service<Request<FacadeJsonBody<ReqBody>>, Response = Response<ResBody>>
+		+ Clone
+		+ Send
+		+ 'static,
+	S::Future: Send,
+	ReqBody: Body<Data = Bytes> + Send + 'static,
+	ResBody: Body<Data = Bytes> + Send + 'static,
+{
+	type Response = Response<FacadeJsonBody<ResBody>>;
+	type Error = S::Error;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+		// use the instance that was polled ready
+		let clone = self.inner.clone();
+		let mut inner = std::mem::replace(&mut self.inner, clone);
+		let config = self.config.clone();
+
+		Box::pin(async move {
+			let request = if config.paths.is_some() && is_json(request.headers()) {
+				let (mut parts, body) = request.into_parts();
+				let Ok(body) = buffer(body, config.body_limit).await else {
+					return Ok(error_response(StatusCode::BAD_REQUEST));
+				};
+
+				let body =
+					body.map_complete(|body| rewrite(&body, &config, decode), &mut parts.headers);
+				Request::from_parts(parts, body)
+			} else {
+				request.map(|body| FacadeJsonBody::passthrough(None, Box::pin(body)))
+			};
+
+			let response = inner.call(request).await?;
+			if !is_json(response.headers()) {
+				return Ok(response.map(|body| FacadeJsonBody::passthrough(None, Box::pin(body))));
+			}
+
+			let (mut parts, body) = response.into_parts();
+			let Ok(body) = buffer(body, config.body_limit).await else {
+				return Ok(error_response(StatusCode::INTERNAL_SERVER_ERROR));
+			};
+
+			let body =
+				body.map_complete(|body| rewrite(&body, &config, encode), &mut parts.headers);
+			Ok(Response::from_parts(parts, body))
+		})
+	}
+}
+
+/// A body read up to the limit.
+enum Buffered<B> {
+	/// The whole body.
+	Complete(Bytes),
+
+	/// The body is over the limit, passed through with the bytes already read.
+	OverLimit(FacadeJsonBody<B>),
+}
+
+impl<B> Buffered<B> {
+	/// Rewrite a complete body, an incomplete one is passed through.
+	fn map_complete(
+		self,
+		f: impl FnOnce(Bytes) -> Bytes,
+		headers: &mut HeaderMap,
+	) -> FacadeJsonBody<B> {
+		match self {
+			Buffered::Complete(body) => {
+				let body = f(body);
+				set_content_length(headers, body.len());
+				FacadeJsonBody::full(body)
+			}
+			Buffered::OverLimit(body) => body,
+		}
+	}
+}
+
+/// Read the body unless it is over `limit` bytes, without losing any bytes when it is.
+async fn buffer<B: Body<Data = Bytes>>(body: B, limit: usize) -> Result<Buffered<B>, B::Error> {
+	let mut body = Box::pin(body);
+	if body.size_hint().lower() > limit as u64 {
+		return Ok(Buffered::OverLimit(FacadeJsonBody::passthrough(None, body)));
+	}
+
+	let mut bytes = Vec::new();
+	while let Some(frame) = body.frame().await {
+		// trailers are dropped, as when collecting
+		let Ok(data) = frame?.into_data() else {
+			continue;
+		};
+
+		bytes.extend_from_slice(&data);
+		if bytes.len() > limit {
+			let buffered = FacadeJsonBody::passthrough(Some(Bytes::from(bytes)), body);
+			return Ok(Buffered::OverLimit(buffered));
+		}
+	}
+
+	Ok(Buffered::Complete(Bytes::from(bytes)))
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+	headers
+		.get(CONTENT_TYPE)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.split(';').next())
+		.is_some_and(|mime| {
+			let mime = mime.trim().to_ascii_lowercase();
+			mime == "application/json"
+				|| (mime.starts_with("application/") && mime.ends_with("+json"))
+		})
+}
+
+fn set_content_length(headers: &mut HeaderMap, len: usize) {
+	headers.insert(CONTENT_LENGTH, HeaderValue::from(len));
+}
+
+fn error_response<B>(status: StatusCode) -> Response<FacadeJsonBody<B>> {
+	let mut response = Response::new(FacadeJsonBody::full(Bytes::new()));
+	*response.status_mut() = status;
+	response
+}
+
+type Transform = fn(Uuid128, &UuidV47Key) -> Option<Uuid128>;
+
+fn encode(uuid: Uuid128, key: &UuidV47Key) -> Option<Uuid128> {
+	(uuid.uuid_version() == 7).then(|| uuid.encode_as_v4facade(key))
+}
+
+fn decode(uuid: Uuid128, key: &UuidV47Key) -> Option<Uuid128> {
+	(uuid.uuid_version() == 4).then(|| uuid.decode_from_v4facade(key))
+}
+
+/// Rewrite the UUID strings of a JSON document, bodies that are not valid JSON are returned unchanged.
+///
+/// The strings are replaced in place (a UUID keeps its length), the rest of the document is copied as is.
+fn rewrite(body: &Bytes, config: &Config, f: Transform) -> Bytes {
+	// validate first, so the scanner below can assume well-formed JSON of bounded depth
+	if serde_json::from_slice::<IgnoredAny>(body).is_err() {
+		return body.clone();
+	}
+
+	let mut scanner = Scanner {
+		body,
+		pos: 0,
+		path: Vec::new(),
+		config,
+		f,
+		edits: Vec::new(),
+	};
+	scanner.value();
+
+	if scanner.edits.is_empty() {
+		return body.clone();
+	}
+
+	let mut out = body.to_vec();
+	for (pos, uuid) in scanner.edits {
+		out[pos..pos + 36].copy_from_slice(uuid.to_string().as_bytes());
+	}
+	Bytes::from(out)
+}
+
+/// Finds the UUID strings to rewrite in a valid JSON document.
+struct Scanner<'a> {
+	body: &'a [u8],
+	pos: usize,
+	/// Current JSON Pointer, only tracked when paths are configured.
+	path: Vec<String>,
+	config: &'a Config,
+	f: Transform,
+	/// Offset and new value of each string to rewrite.
+	edits: Vec<(usize, Uuid128)>,
+}
+
+impl Scanner<'_> {
+	fn peek(&self) -> Option<u8> {
+		self.body.get(self.pos).copied()
+	}
+
+	fn skip_whitespace(&mut self) {
+		while self
+			.peek()
+			.is_some_and(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+		{
+			self.pos += 1;
+		}
+	}
+
+	fn value(&mut self) {
+		self.skip_whitespace();
+		match self.peek() {
+			Some(b'{') => self.object(),
+			Some(b'[') => self.array(),
+			Some(b'"') => {
+				let (start, end) = self.string();
+				if end - start == 36 && matches_path(&self.path, self.config.paths.as_deref()) {
+					let uuid = std::str::from_utf8(&self.body[start..end])
+						.ok()
+						.and_then(|s| s.parse().ok())
+						.and_then(|uuid| (self.f)(uuid, &self.config.key));
+					if let Some(uuid) = uuid {
+						self.edits.push((start, uuid));
+					}
+				}
+			}
+			// number, `true`, `false` or `null`
+			_ => {
+				while self.peek().is_some_and(|b| {
+					!matches!(b, b',' | b']' | b'}' | b' ' | b'\t' | b'\n' | b'\r')
+				}) {
+					self.pos += 1;
+				}
+			}
+		}
+	}
+
+	fn object(&mut self) {
+		self.pos += 1;
+		loop {
+			self.skip_whitespace();
+			match self.peek() {
+				Some(b'}') | None => break,
+				Some(b',') => {
+					self.pos += 1;
+					continue;
+				}
+				_ => {}
+			}
+
+			let (start, end) = self.string();
+			self.skip_whitespace();
+			self.pos += 1; // `:`
+
+			if self.config.paths.is_some() {
+				let raw = &self.body[start..end];
+				let name = if raw.contains(&b'\\') {
+					serde_json::from_slice(&self.body[start - 1..=end]).unwrap_or_default()
+				} else {
+					String::from_utf8_lossy(raw).into_owned()
+				};
+
+				self.path.push(name);
+				self.value();
+				self.path.pop();
+			} else {
+				self.value();
+			}
+		}
+		self.pos += 1;
+	}
+
+	fn array(&mut self) {
+		self.pos += 1;
+		let mut index = 0usize;
+		loop {
+			self.skip_whitespace();
+			match self.peek() {
+				Some(b']') | None => break,
+				Some(b',') => {
+					self.pos += 1;
+					continue;
+				}
+				_ => {}
+			}
+
+			if self.config.paths.is_some() {
+				self.path.push(index.to_string());
+				self.value();
+				self.path.pop();
+			} else {
+				self.value();
+			}
+			index += 1;
+		}
+		self.pos += 1;
+	}
+
+	/// Skip a string, returns the range of its contents (without quotes).
+	fn string(&mut self) -> (usize, usize) {
+		let start = self.pos + 1;
+		let mut end = start;
+		while let Some(&b) = self.body.get(end) {
+			match b {
+				b'\\' => end += 2,
+				b'"' => break,
+				_ => end += 1,
+			}
+		}
+
+		self.pos = end + 1;
+		(start, end.min(self.body.len()))
+	}
+}
+
+fn matches_path(path: &[String], patterns: Option<&[Vec<String>]>) -> bool {
+	let Some(patterns) = patterns else {
+		return true;
+	};
+
+	patterns.iter().any(|pattern| {
+		pattern.len() == path.len()
+			&& pattern
+				.iter()
+				.zip(path)
+				.all(|(expected, actual)| expected == "*" || expected == actual)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::Value;
+
+	use super::*;
+
+	const V7: &str = "00000000-0000-7000-8000-000000000000";
+	const FACADE: &str = "22d97126-9609-4000-8000-000000000000";
+
+	fn key() -> UuidV47Key {
+		UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210)
+	}
+
+	#[test]
+	fn test_rewrite_paths() {
+		let layer = FacadeJsonLayer::new_allow_weak_key(key()).with_paths(["/items/*/id", "/a~1b"]);
+
+		let body = Bytes::from(format!(
+			r#"{{"id":"{V7}","items":[{{"id":"{V7}"}}],"a/b":"{V7}","a\/b":"{V7}"}}"#
+		));
+		let out: Value = serde_json::from_slice(&rewrite(&body, &layer.config, encode)).unwrap();
+
+		assert_eq!(out["id"], V7);
+		assert_eq!(out["items"][0]["id"], FACADE);
+		assert_eq!(out["a/b"], FACADE);
+
+		// not JSON
+		let body = Bytes::from_static(b"not json");
+		assert_eq!(rewrite(&body, &layer.config, encode), body);
+	}
+
+	#[test]
+	fn test_rewrite_preserves_document() {
+		let layer = FacadeJsonLayer::new_allow_weak_key(key());
+
+		let body = format!(
+			r#"{{ "z": 1, "big": 123456789012345678901234567890, "id": "{V7}",
+	"f": 0.10000000000000000555, "e": 1e400, "nested": [ {{ "s": "\"{V7}" }}, "{V7}", null, true ], "a": "{V7}" }}"#
+		);
+		let expected = body
+			.replace(&format!(r#": "{V7}""#), &format!(r#": "{FACADE}""#))
+			.replace(&format!(r#", "{V7}""#), &format!(r#", "{FACADE}""#));
+
+		let out = rewrite(&Bytes::from(body.clone()), &layer.config, encode);
+		assert_eq!(String::from_utf8(out.to_vec()).unwrap(), expected);
+		assert_ne!(expected, body);
+	}
+
+	/// A body of several chunks without size hint.
+	struct Chunks(Vec<&'static str>);
+
+	impl Body for Chunks {
+		type Data = Bytes;
+		type Error = std::convert::Infallible;
+
+		fn poll_frame(
+			self: Pin<&mut Self>,
+			_: &mut Context<'_>,
+		) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+			let chunks = &mut self.get_mut().0;
+			Poll::Ready(
+				(!chunks.is_empty()).then(|| Ok(Frame::data(Bytes::from(chunks.remove(0))))),
+			)
+		}
+	}
+
+	#[tokio::test]
+	async fn test_buffer_over_limit() {
+		let body = Chunks(vec![
+			r#"{"id":"#,
+			r#""00000000-0000-7000-8000-000000000000""#,
+			"}",
+		]);
+		let Ok(Buffered::OverLimit(body)) = buffer(body, 16).await else {
+			panic!("body is over the limit");
+		};
+		let body = body.collect().await.unwrap().to_bytes();
+		assert_eq!(body, format!(r#"{{"id":"{V7}"}}"#));
+
+		let body = Chunks(vec![r#"{"id":"#, "1}"]);
+		let Ok(Buffered::Complete(body)) = buffer(body, 16).await else {
+			panic!("body is under the limit");
+		};
+		assert_eq!(body, r#"{"id":1}"#);
+	}
+}
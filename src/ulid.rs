@@ -0,0 +1,215 @@
+use crate::builder::UuidV7Builder;
+use crate::encoding::{parse_base32, BASE32_LEN};
+use crate::error::{Base32Error, UlidError};
+use crate::uuid::Uuid128;
+
+/// ULID bits overwritten by the `UUIDv7` version (76-79) and variant (62-63).
+const OVERWRITTEN_BITS: u128 = (0xF << 76) | (0b11 << 62);
+
+impl Uuid128 {
+	/// Convert a 128-bit ULID into `UUIDv7`.
+	///
+	/// Both share the 48-bit millisecond timestamp, the ULID randomness is kept in place
+	/// except for the 6 bits overwritten by the `UUIDv7` version and variant:
+	///
+	/// * bits 76-79 (the 4 most significant random bits) become the version `0b0111`.
+	/// * bits 62-63 become the variant `0b10`.
+	///
+	/// 74 of the 80 random bits are preserved, so different ULIDs can give the same `UUIDv7`:
+	/// ULIDs differing only in these bits collide, and a monotonic increment carrying into bit 62
+	/// (`0x3FFF_FFFF_FFFF_FFFF` + 1 in the low 64 bits) breaks the order.<br>
+	/// Use [`Uuid128::try_from_ulid`] to reject ULIDs with these bits set.
+	///
+	/// # Examples
+	/// ```
+	/// let v7 = uuid47::Uuid128::from_ulid(0x0191_2345_6789_ffff_ffff_ffff_ffff_ffff);
+	/// assert_eq!(v7.to_string(), "01912345-6789-7fff-bfff-ffffffffffff");
+	/// ```
+	#[must_use]
+	pub fn from_ulid(ulid: u128) -> Self {
+		#[allow(clippy::cast_possible_truncation)]
+		UuidV7Builder::new()
+			.timestamp_ms((ulid >> 80) as u64)
+			.rand_a((ulid >> 64) as u16)
+			.rand_b(ulid as u64)
+			.build()
+	}
+
+	/// Convert a 128-bit ULID into `UUIDv7` without losing any bit, see [`Uuid128::from_ulid`].
+	///
+	/// The conversion is injective and keeps the order of the accepted ULIDs,
+	/// which must have bits 76-79 and 62-63 clear (e.g. monotonic generators limited to 62 bits of increments).<br>
+	/// [`Uuid128::to_ulid_stripped`] converts back to the original ULID.
+	///
+	/// # Errors
+	///
+	/// Returns [`UlidError::OverwrittenBits`] if any of bits 76-79 and 62-63 is set.
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{Uuid128, UlidError};
+	///
+	/// let v7 = Uuid128::try_from_ulid(0x0191_2345_6789_0fff_3fff_ffff_ffff_ffff).unwrap();
+	/// assert_eq!(v7.to_string(), "01912345-6789-7fff-bfff-ffffffffffff");
+	///
+	/// assert_eq!(v7.to_ulid_stripped(), 0x0191_2345_6789_0fff_3fff_ffff_ffff_ffff);
+	///
+	/// assert_eq!(Uuid128::try_from_ulid(0x0191_2345_6789_0fff_4000_0000_0000_0000), Err(UlidError::OverwrittenBits));
+	/// ```
+	pub fn try_from_ulid(ulid: u128) -> Result<Self, UlidError> {
+		if ulid & OVERWRITTEN_BITS != 0 {
+			return Err(UlidError::OverwrittenBits);
+		}
+
+		Ok(Self::from_ulid(ulid))
+	}
+
+	/// Convert into a 128-bit ULID.
+	///
+	/// Lossless: every 128-bit value is a ULID, and for a `UUIDv7` it has the same timestamp.<br>
+	/// `Uuid128::from_ulid(v7.to_ulid()) == v7` for every `UUIDv7`.
+	/// The version and variant bits are kept, use [`Uuid128::to_ulid_stripped`] to invert [`Uuid128::try_from_ulid`].
+	#[must_use]
+	pub fn to_ulid(&self) -> u128 {
+		self.to_u128()
+	}
+
+	/// Convert into a 128-bit ULID with the version and variant bits (76-79 and 62-63) cleared.
+	///
+	/// Inverse of [`Uuid128::try_from_ulid`]: `Uuid128::try_from_ulid(ulid)?.to_ulid_stripped() == ulid`.
+	#[must_use]
+	pub fn to_ulid_stripped(&self) -> u128 {
+		self.to_u128() & !OVERWRITTEN_BITS
+	}
+
+	/// Parse a 26-character ULID string and convert it into `UUIDv7`, see [`Uuid128::from_ulid`].
+	///
+	/// ULID strings are case-insensitive Crockford base32.
+	///
+	/// # Errors
+	///
+	/// * [`Base32Error::InvalidLength`] - if the string is not 26 characters long.
+	/// * [`Base32Error::InvalidCharacter`] - if a character is outside the Crockford alphabet.
+	/// * [`Base32Error::Overflow`] - if the first character is greater than `7`.
+	///
+	/// # Examples
+	/// ```
+	/// let v7 = uuid47::Uuid128::from_ulid_str("01ARZ3NDEKTSV4RRFFQ69G5FAV").unwrap();
+	/// assert_eq!(v7.timestamp_ms(), 1_469_922_850_259);
+	/// // version and variant bits overwritten
+	/// assert_eq!(v7.to_ulid_string(), "01ARZ3NDEKESV8RRFFQ69G5FAV");
+	/// ```
+	pub fn from_ulid_str(s: &str) -> Result<Self, Base32Error> {
+		let s = s.as_bytes();
+		if s.len() != BASE32_LEN {
+			return Err(Base32Error::InvalidLength);
+		}
+
+		let mut upper = [0u8; BASE32_LEN];
+		upper.copy_from_slice(s);
+		upper.make_ascii_uppercase();
+
+		parse_base32(&upper).map(Self::from_ulid)
+	}
+
+	/// Format as a 26-character uppercase ULID string.
+	///
+	/// Same as [`Uuid128::to_base32`], the string sorts like the `UUIDv7`.
+	#[must_use]
+	pub fn to_ulid_string(&self) -> String {
+		self.to_base32()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_ulid_lost_bits() {
+		let ulid = u128::MAX;
+		let v7 = Uuid128::from_ulid(ulid);
+		let lost = ulid ^ v7.to_ulid();
+		assert_eq!(lost, (0b1000 << 76) | (0b01 << 62));
+		assert_eq!(lost.count_ones() + v7.to_ulid().count_ones(), 128);
+
+		let ulid = 0;
+		let v7 = Uuid128::from_ulid(ulid);
+		assert_eq!(v7.to_ulid() ^ ulid, (0b0111 << 76) | (0b10 << 62));
+
+		// lossless from v7
+		let v7: Uuid128 = "01912345-6789-7abc-9def-0123456789ab".parse().unwrap();
+		assert_eq!(Uuid128::from_ulid(v7.to_ulid()), v7);
+		assert_eq!(Uuid128::from_ulid_str(&v7.to_ulid_string()), Ok(v7));
+	}
+
+	#[test]
+	fn test_ulid_monotonic() {
+		let base = 0x0191_2345_6789_0000_0000_0000_0000_0000u128;
+		let v7: Vec<Uuid128> = (0..1024).map(|i| Uuid128::from_ulid(base + i)).collect();
+
+		assert!(v7.windows(2).all(|w| w[0] < w[1]));
+		assert!(v7.iter().all(|uuid| uuid.uuid_version() == 7));
+	}
+
+	#[test]
+	fn test_ulid_carry() {
+		let base = 0x0191_2345_6789_0000_0000_0000_0000_0000u128;
+		let before = base | 0x3FFF_FFFF_FFFF_FFFF;
+		let after = before + 1;
+
+		// the carry into bit 62 is overwritten by the variant
+		assert!(Uuid128::from_ulid(after) < Uuid128::from_ulid(before));
+		assert_eq!(
+			Uuid128::from_ulid(after),
+			Uuid128::from_ulid(base | 0x8000_0000_0000_0000)
+		);
+
+		assert!(Uuid128::try_from_ulid(before).is_ok());
+		assert_eq!(
+			Uuid128::try_from_ulid(after),
+			Err(UlidError::OverwrittenBits)
+		);
+		assert_eq!(
+			Uuid128::try_from_ulid(base | (1 << 76)),
+			Err(UlidError::OverwrittenBits)
+		);
+	}
+
+	#[test]
+	fn test_ulid_stripped_roundtrip() {
+		let mut ulid = 0x0191_2345_6789_0000_0000_0000_0000_0000u128;
+		for _ in 0..1024 {
+			// xorshift over all 128 bits, then clear the overwritten ones
+			ulid ^= ulid << 13;
+			ulid ^= ulid >> 7;
+			ulid ^= ulid << 17;
+			ulid = ulid.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+			let accepted = ulid & !OVERWRITTEN_BITS;
+			let v7 = Uuid128::try_from_ulid(accepted).unwrap();
+			assert_eq!(v7.uuid_version(), 7);
+			assert_eq!(v7.to_ulid_stripped(), accepted);
+			assert_eq!(v7.to_ulid(), accepted | (0b0111 << 76) | (0b10 << 62));
+		}
+	}
+
+	#[test]
+	fn test_ulid_str() {
+		let upper = Uuid128::from_ulid_str("01ARZ3NDEKTSV4RRFFQ69G5FAV");
+		assert_eq!(Uuid128::from_ulid_str("01arz3ndektsv4rrffq69g5fav"), upper);
+
+		assert_eq!(
+			Uuid128::from_ulid_str("01ARZ3NDEKTSV4RRFFQ69G5FA"),
+			Err(Base32Error::InvalidLength)
+		);
+		assert_eq!(
+			Uuid128::from_ulid_str("01ARZ3NDEKTSV4RRFFQ69G5FAU"),
+			Err(Base32Error::InvalidCharacter)
+		);
+		assert_eq!(
+			Uuid128::from_ulid_str("81ARZ3NDEKTSV4RRFFQ69G5FAV"),
+			Err(Base32Error::Overflow)
+		);
+	}
+}
@@ -1,9 +1,25 @@
+use crate::builder::UuidV7Builder;
 use crate::error::{UuidParseError, UuidValidationError};
+use crate::feistel;
 use crate::key::UuidV47Key;
-use crate::utils::{hexval, read_48_big_endian, siphash24, write_48_big_endian};
+use crate::prf::FacadePrf;
+use crate::scheme::FacadeScheme;
+use crate::sentinel::SentinelPolicy;
+use crate::utils::{hexval, read_48_big_endian, write_48_big_endian};
+
+/// The largest timestamp of a `UUIDv7` (48 bits).
+pub(crate) const MAX_TIMESTAMP_MS: u64 = 0xFFFF_FFFF_FFFF;
 
 /// A 128-bit UUID (`UUIDv4` or `UUIDv7`).
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+///
+/// Ordering follows the big-endian byte order, so `UUIDv7` are ordered by their timestamp.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(
+	feature = "diesel",
+	derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = diesel::sql_types::Binary))]
+#[cfg_attr(feature = "diesel-postgres", diesel(sql_type = diesel::sql_types::Uuid))]
 pub struct Uuid128 {
 	bytes: [u8; 16],
 }
@@ -27,19 +43,58 @@ impl Uuid128 {
 		out
 	}
 
+	/// Create the Nil UUID (all bits zero).
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::nil();
+	/// assert_eq!(uuid.to_string(), "00000000-0000-0000-0000-000000000000");
+	/// ```
+	#[must_use]
+	pub const fn nil() -> Self {
+		Self { bytes: [0x00; 16] }
+	}
+
+	/// Create the Max UUID (all bits one).
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::max();
+	/// assert_eq!(uuid.to_string(), "ffffffff-ffff-ffff-ffff-ffffffffffff");
+	/// ```
+	#[must_use]
+	pub const fn max() -> Self {
+		Self { bytes: [0xFF; 16] }
+	}
+
 	/// Create a UUID from raw 16 bytes
 	///
 	/// Always validate version and variant bits.<br>
-	/// Returns an error if the bytes do not represent a valid `UUIDv4` or `UUIDv7` (RFC 4122).
+	/// Returns an error if the bytes do not represent a valid `UUIDv4` or `UUIDv7` (RFC 4122).<br>
+	/// The Nil and Max UUID (RFC 9562) are accepted as well.
+	///
+	/// `UUIDv8` facades are only accepted by `Uuid128::from_bytes_v8facade` (`v8-facade` feature).
 	///
 	/// # Errors
 	///
 	/// * [`UuidValidationError::InvalidVersion`] - if the version is not 4 or 7.
 	/// * [`UuidValidationError::InvalidVariant`] - if the variant is not RFC 4122.
 	pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, UuidValidationError> {
-		// Accept only version 4 or 7
+		Self::from_bytes_with_versions(bytes, &[4, 7])
+	}
+
+	/// Validate like [`Uuid128::from_bytes`], accepting the given versions.
+	pub(crate) fn from_bytes_with_versions(
+		bytes: [u8; 16],
+		versions: &[u8],
+	) -> Result<Self, UuidValidationError> {
+		// Nil and Max UUID are valid without version and variant bits
+		if bytes == [0x00; 16] || bytes == [0xFF; 16] {
+			return Ok(Self { bytes });
+		}
+
 		let version = (bytes[6] >> 4) & 0x0F;
-		if version != 4 && version != 7 {
+		if !versions.contains(&version) {
 			return Err(UuidValidationError::InvalidVersion);
 		}
 
@@ -52,6 +107,30 @@ impl Uuid128 {
 		Ok(Self { bytes })
 	}
 
+	/// Create a UUID from bytes built by a transform of this crate, with valid version and variant bits.
+	#[cfg(feature = "v8-facade")]
+	pub(crate) const fn from_valid_bytes(bytes: [u8; 16]) -> Self {
+		Self { bytes }
+	}
+
+	/// Create a UUID from a big-endian `u128`.
+	///
+	/// Validates the same way as [`Uuid128::from_bytes`]: the Nil (`0`) and Max (`u128::MAX`) UUID are accepted.
+	///
+	/// # Errors
+	///
+	/// * [`UuidValidationError::InvalidVersion`] - if the version is not 4 or 7 (and the value is not Nil or Max).
+	/// * [`UuidValidationError::InvalidVariant`] - if the variant is not RFC 4122.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::from_u128(0x00000000_0000_7000_8000_000000000000).unwrap();
+	/// assert_eq!(uuid, uuid47::Uuid128::empty());
+	/// ```
+	pub fn from_u128(value: u128) -> Result<Self, UuidValidationError> {
+		Self::from_bytes(value.to_be_bytes())
+	}
+
 	/// Create a UUID from raw 16 bytes **without validating**.
 	///
 	/// # Safety
@@ -63,6 +142,47 @@ impl Uuid128 {
 		Self { bytes }
 	}
 
+	/// Get the raw 16 bytes of this UUID.
+	#[must_use]
+	#[inline]
+	pub fn as_bytes(&self) -> &[u8; 16] {
+		&self.bytes
+	}
+
+	/// Consume this UUID and return the raw 16 bytes.
+	#[must_use]
+	#[inline]
+	pub fn into_bytes(self) -> [u8; 16] {
+		self.bytes
+	}
+
+	/// Get this UUID as a big-endian `u128`.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::Uuid128::empty();
+	/// assert_eq!(uuid.to_u128(), 0x00000000_0000_7000_8000_000000000000);
+	/// ```
+	#[must_use]
+	#[inline]
+	pub fn to_u128(&self) -> u128 {
+		u128::from_be_bytes(self.bytes)
+	}
+
+	/// Check whether this is the Nil UUID.
+	#[must_use]
+	#[inline]
+	pub fn is_nil(&self) -> bool {
+		self.bytes == [0x00; 16]
+	}
+
+	/// Check whether this is the Max UUID.
+	#[must_use]
+	#[inline]
+	pub fn is_max(&self) -> bool {
+		self.bytes == [0xFF; 16]
+	}
+
 	/// Get this UUID version.
 	///
 	/// Returns 4 for `UUIDv4`, 7 for `UUIDv7`, or other values for invalid versions.
@@ -71,6 +191,110 @@ impl Uuid128 {
 		(&self.bytes[6] >> 4) & 0x0F
 	}
 
+	/// Get the 48-bit Unix timestamp in milliseconds of this `UUIDv7`.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::UuidV7Builder::new().timestamp_ms(1_700_000_000_000).build();
+	/// assert_eq!(uuid.timestamp_ms(), 1_700_000_000_000);
+	/// ```
+	#[must_use]
+	#[inline]
+	pub fn timestamp_ms(&self) -> u64 {
+		read_48_big_endian((self.bytes[0..6]).try_into().unwrap())
+	}
+
+	/// Create the smallest `UUIDv7` for the given millisecond.
+	///
+	/// All random bits are zero, so this is an inclusive lower bound for range scans.<br>
+	/// Timestamps above 48 bits are clamped to the largest `UUIDv7` timestamp.
+	///
+	/// # Examples
+	/// ```
+	/// let lo = uuid47::Uuid128::min_for_timestamp(0x0123_4567_89AB);
+	/// assert_eq!(lo.to_string(), "01234567-89ab-7000-8000-000000000000");
+	/// ```
+	#[must_use]
+	pub fn min_for_timestamp(timestamp_ms: u64) -> Self {
+		UuidV7Builder::new()
+			.timestamp_ms(timestamp_ms.min(MAX_TIMESTAMP_MS))
+			.build()
+	}
+
+	/// Create the largest `UUIDv7` for the given millisecond.
+	///
+	/// All random bits are one, so this is an inclusive upper bound for range scans.<br>
+	/// Timestamps above 48 bits are clamped to the largest `UUIDv7` timestamp.
+	///
+	/// # Examples
+	/// ```
+	/// let hi = uuid47::Uuid128::max_for_timestamp(0x0123_4567_89AB);
+	/// assert_eq!(hi.to_string(), "01234567-89ab-7fff-bfff-ffffffffffff");
+	/// ```
+	#[must_use]
+	pub fn max_for_timestamp(timestamp_ms: u64) -> Self {
+		UuidV7Builder::new()
+			.timestamp_ms(timestamp_ms.min(MAX_TIMESTAMP_MS))
+			.rand_a(0x0FFF)
+			.rand_b(0x3FFF_FFFF_FFFF_FFFF)
+			.build()
+	}
+
+	/// Get the 12-bit `rand_a` field of this `UUIDv7`.
+	///
+	/// These are the low 4 bits of byte 6 and all of byte 7.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::UuidV7Builder::new().rand_a(0xABC).build();
+	/// assert_eq!(uuid.rand_a(), 0xABC);
+	/// ```
+	#[must_use]
+	#[inline]
+	pub fn rand_a(&self) -> u16 {
+		u16::from_be_bytes([self.bytes[6] & 0x0F, self.bytes[7]])
+	}
+
+	/// Get the 62-bit `rand_b` field of this `UUIDv7`.
+	///
+	/// These are the low 6 bits of byte 8 and all of bytes 9 to 15.
+	///
+	/// # Examples
+	/// ```
+	/// let uuid = uuid47::UuidV7Builder::new().rand_b(0x0123_4567_89AB_CDEF).build();
+	/// assert_eq!(uuid.rand_b(), 0x0123_4567_89AB_CDEF);
+	/// ```
+	#[must_use]
+	#[inline]
+	pub fn rand_b(&self) -> u64 {
+		let mut b = [0u8; 8];
+		b.copy_from_slice(&self.bytes[8..16]);
+		b[0] &= 0x3F;
+		u64::from_be_bytes(b)
+	}
+
+	/// Set the 48-bit big-endian timestamp in bytes 0 to 5.
+	pub(crate) fn set_timestamp_ms(&mut self, ts_ms_48: u64) {
+		write_48_big_endian(
+			(&mut self.bytes[0..6]).try_into().unwrap(),
+			ts_ms_48 & 0x0000_FFFF_FFFF_FFFFu64,
+		);
+	}
+
+	/// Set the 12-bit `rand_a` field, keeping the version bits.
+	pub(crate) fn set_rand_a(&mut self, rand_a_12: u16) {
+		let [hi, lo] = rand_a_12.to_be_bytes();
+		self.bytes[6] = (self.bytes[6] & 0xF0) | (hi & 0x0F);
+		self.bytes[7] = lo;
+	}
+
+	/// Set the 62-bit `rand_b` field, keeping the variant bits.
+	pub(crate) fn set_rand_b(&mut self, rand_b_62: u64) {
+		let b = rand_b_62.to_be_bytes();
+		self.bytes[8] = (self.bytes[8] & 0xC0) | (b[0] & 0x3F);
+		self.bytes[9..16].copy_from_slice(&b[1..8]);
+	}
+
 	/// Set the UUID version (4 or 7).
 	///
 	/// set the version bits (4 bits) in byte 6.
@@ -87,6 +311,8 @@ impl Uuid128 {
 
 	/// Encode this `UUIDv7` into `UUIDv4` facade using `UuidV47Key`.
 	///
+	/// The Nil and Max UUID are returned unchanged.
+	///
 	/// # Panics
 	///
 	/// This function does not validate the input `UUIDv7`.
@@ -94,11 +320,39 @@ impl Uuid128 {
 	#[must_use]
 	#[inline]
 	pub fn encode_as_v4facade(&self, key: &UuidV47Key) -> Uuid128 {
-		//* 1. SipHash24(key, v7.random74bits) -> take low 48 bits */
+		self.encode_as_v4facade_with_prf(key)
+	}
+
+	/// Decode this `UUIDv4` facade back into `UUIDv7` using `UuidV47Key`.
+	///
+	/// The Nil and Max UUID are returned unchanged.
+	///
+	/// # Panics
+	///
+	/// This function does not validate the input `UUIDv4` facade.
+	/// So, invalid input may occur panic.
+	#[must_use]
+	#[inline]
+	pub fn decode_from_v4facade(&self, key: &UuidV47Key) -> Uuid128 {
+		self.decode_from_v4facade_with_prf(key)
+	}
+
+	/// Encode this `UUIDv7` into `UUIDv4` facade, masking the timestamp with the given [`FacadePrf`].
+	///
+	/// With a [`UuidV47Key`] this is the same as [`Uuid128::encode_as_v4facade`].<br>
+	/// The Nil and Max UUID are returned unchanged.
+	#[must_use]
+	#[inline]
+	pub fn encode_as_v4facade_with_prf<P: FacadePrf + ?Sized>(&self, prf: &P) -> Uuid128 {
+		if self.is_nil() || self.is_max() {
+			return *self;
+		}
+
+		//* 1. PRF(key, v7.random74bits) -> take low 48 bits */
 		let mut sipmsg = [0u8; 10];
 
 		build_sip_input_from_v7(self, &mut sipmsg);
-		let mask48 = siphash24(&sipmsg, key.k0, key.k1) & 0x0000_FFFF_FFFF_FFFFu64;
+		let mask48 = prf.prf(&sipmsg) & 0x0000_FFFF_FFFF_FFFFu64;
 
 		//* 2. Encode timestamp */
 		let encoded_timestamp = read_48_big_endian((self.bytes[0..6]).try_into().unwrap()) ^ mask48;
@@ -120,19 +374,21 @@ impl Uuid128 {
 		out
 	}
 
-	/// Decode this `UUIDv4` facade back into `UUIDv7` using `UuidV47Key`.
+	/// Decode this `UUIDv4` facade back into `UUIDv7` with the [`FacadePrf`] it was encoded with.
 	///
-	/// # Panics
-	///
-	/// This function does not validate the input `UUIDv4` facade.
-	/// So, invalid input may occur panic.
+	/// With a [`UuidV47Key`] this is the same as [`Uuid128::decode_from_v4facade`].<br>
+	/// The Nil and Max UUID are returned unchanged.
 	#[must_use]
 	#[inline]
-	pub fn decode_from_v4facade(&self, key: &UuidV47Key) -> Uuid128 {
+	pub fn decode_from_v4facade_with_prf<P: FacadePrf + ?Sized>(&self, prf: &P) -> Uuid128 {
+		if self.is_nil() || self.is_max() {
+			return *self;
+		}
+
 		// 1. rebuild same Sip input from facade (identical bytes)
 		let mut sipmsg = [0u8; 10];
 		build_sip_input_from_v7(self, &mut sipmsg);
-		let mask48 = siphash24(&sipmsg, key.k0, key.k1) & 0x0000_FFFF_FFFF_FFFFu64;
+		let mask48 = prf.prf(&sipmsg) & 0x0000_FFFF_FFFF_FFFFu64;
 
 		// 2. ts = encTS ^ mask
 		// Force slice to fixed-size (should not panic)
@@ -150,6 +406,214 @@ impl Uuid128 {
 		out.set_variant_rfc4122();
 		out
 	}
+
+	/// Encode this `UUIDv7` into `UUIDv4` facade using the given `FacadeScheme`.
+	///
+	/// [`FacadeScheme::Reference`] is the same as [`Uuid128::encode_as_v4facade`].<br>
+	/// The Nil and Max UUID are returned unchanged.
+	///
+	/// Both schemes use SipHash-2-4 with the `UuidV47Key`, other [`FacadePrf`] backends are not supported here.
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{FacadeScheme, Uuid128, UuidV47Key};
+	///
+	/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	/// let v7 = Uuid128::empty();
+	///
+	/// let facade = v7.encode_as_v4facade_with(&key, FacadeScheme::FullWidthV1);
+	/// assert_eq!(facade.uuid_version(), 4);
+	/// assert_eq!(facade.decode_from_v4facade_with(&key, FacadeScheme::FullWidthV1), v7);
+	/// ```
+	#[must_use]
+	#[inline]
+	pub fn encode_as_v4facade_with(&self, key: &UuidV47Key, scheme: FacadeScheme) -> Uuid128 {
+		match scheme {
+			FacadeScheme::Reference => self.encode_as_v4facade(key),
+			FacadeScheme::FullWidthV1 => {
+				if self.is_nil() || self.is_max() {
+					return *self;
+				}
+
+				let mut out = *self;
+				out.set_payload(feistel::permute(self.payload(), key));
+				out.set_version(4);
+				out.set_variant_rfc4122();
+				out
+			}
+		}
+	}
+
+	/// Decode this `UUIDv4` facade back into `UUIDv7` using the given `FacadeScheme`.
+	///
+	/// [`FacadeScheme::Reference`] is the same as [`Uuid128::decode_from_v4facade`].<br>
+	/// The Nil and Max UUID are returned unchanged.
+	#[must_use]
+	#[inline]
+	pub fn decode_from_v4facade_with(&self, key: &UuidV47Key, scheme: FacadeScheme) -> Uuid128 {
+		match scheme {
+			FacadeScheme::Reference => self.decode_from_v4facade(key),
+			FacadeScheme::FullWidthV1 => {
+				if self.is_nil() || self.is_max() {
+					return *self;
+				}
+
+				let mut out = *self;
+				out.set_payload(feistel::unpermute(self.payload(), key));
+				out.set_version(7);
+				out.set_variant_rfc4122();
+				out
+			}
+		}
+	}
+
+	/// Get the 122 bits excluding version and variant, as `[timestamp][rand_a][rand_b]`.
+	#[inline]
+	fn payload(&self) -> u128 {
+		(u128::from(self.timestamp_ms()) << 74)
+			| (u128::from(self.rand_a()) << 62)
+			| u128::from(self.rand_b())
+	}
+
+	/// Set the 122 bits excluding version and variant, as `[timestamp][rand_a][rand_b]`.
+	#[inline]
+	#[allow(clippy::cast_possible_truncation)]
+	fn set_payload(&mut self, payload: u128) {
+		self.set_timestamp_ms((payload >> 74) as u64);
+		self.set_rand_a((payload >> 62) as u16 & 0x0FFF);
+		self.set_rand_b(payload as u64);
+	}
+
+	/// Encode this `UUIDv7` into `UUIDv4` facade, validating the input first.
+	///
+	/// The Nil and Max UUID are handled according to `policy`.<br>
+	/// Takes a [`UuidV47Key`] or any other [`FacadePrf`], like [`Uuid128::encode_as_v4facade_with_prf`].
+	///
+	/// # Errors
+	///
+	/// * [`UuidValidationError::Sentinel`] - if this is the Nil or Max UUID and `policy` is [`SentinelPolicy::Reject`].
+	/// * [`UuidValidationError::InvalidVersion`] - if this is not a `UUIDv7`.
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{SentinelPolicy, Uuid128, UuidV47Key};
+	///
+	/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	/// let nil = Uuid128::nil();
+	///
+	/// assert_eq!(nil.checked_encode_as_v4facade(&key, SentinelPolicy::Passthrough).unwrap(), nil);
+	/// assert!(nil.checked_encode_as_v4facade(&key, SentinelPolicy::Reject).is_err());
+	/// ```
+	pub fn checked_encode_as_v4facade<P: FacadePrf + ?Sized>(
+		&self,
+		key: &P,
+		policy: SentinelPolicy,
+	) -> Result<Uuid128, UuidValidationError> {
+		self.check_sentinel(policy)?;
+		if self.is_nil() || self.is_max() {
+			return Ok(*self);
+		}
+
+		if self.uuid_version() != 7 {
+			return Err(UuidValidationError::InvalidVersion);
+		}
+
+		Ok(self.encode_as_v4facade_with_prf(key))
+	}
+
+	/// Decode this `UUIDv4` facade back into `UUIDv7`, validating the input first.
+	///
+	/// The Nil and Max UUID are handled according to `policy`.<br>
+	/// Takes the [`UuidV47Key`] or [`FacadePrf`] the facade was encoded with.
+	///
+	/// # Errors
+	///
+	/// * [`UuidValidationError::Sentinel`] - if this is the Nil or Max UUID and `policy` is [`SentinelPolicy::Reject`].
+	/// * [`UuidValidationError::InvalidVersion`] - if this is not a `UUIDv4`.
+	pub fn checked_decode_from_v4facade<P: FacadePrf + ?Sized>(
+		&self,
+		key: &P,
+		policy: SentinelPolicy,
+	) -> Result<Uuid128, UuidValidationError> {
+		self.check_sentinel(policy)?;
+		if self.is_nil() || self.is_max() {
+			return Ok(*self);
+		}
+
+		if self.uuid_version() != 4 {
+			return Err(UuidValidationError::InvalidVersion);
+		}
+
+		Ok(self.decode_from_v4facade_with_prf(key))
+	}
+
+	/// Fail if this is the Nil or Max UUID and `policy` rejects them.
+	fn check_sentinel(&self, policy: SentinelPolicy) -> Result<(), UuidValidationError> {
+		match policy {
+			SentinelPolicy::Reject if self.is_nil() || self.is_max() => {
+				Err(UuidValidationError::Sentinel)
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Decode this `UUIDv4` facade and return the smallest `UUIDv7` of the same millisecond.
+	///
+	/// Useful as a pagination cursor when the client only sends back a facade.
+	#[must_use]
+	#[inline]
+	pub fn min_for_v4facade<P: FacadePrf + ?Sized>(&self, key: &P) -> Uuid128 {
+		Self::min_for_timestamp(self.decode_from_v4facade_with_prf(key).timestamp_ms())
+	}
+}
+
+impl AsRef<[u8]> for Uuid128 {
+	fn as_ref(&self) -> &[u8] {
+		&self.bytes
+	}
+}
+
+impl From<Uuid128> for [u8; 16] {
+	fn from(uuid: Uuid128) -> Self {
+		uuid.bytes
+	}
+}
+
+impl From<Uuid128> for u128 {
+	fn from(uuid: Uuid128) -> Self {
+		uuid.to_u128()
+	}
+}
+
+impl TryFrom<[u8; 16]> for Uuid128 {
+	type Error = UuidValidationError;
+
+	fn try_from(bytes: [u8; 16]) -> Result<Self, Self::Error> {
+		Self::from_bytes(bytes)
+	}
+}
+
+impl TryFrom<&[u8]> for Uuid128 {
+	type Error = UuidValidationError;
+
+	/// Create a UUID from a byte slice.
+	///
+	/// Validates the same way as [`Uuid128::from_bytes`] and additionally fails with
+	/// [`UuidValidationError::InvalidLength`] if the slice is not 16 bytes long.
+	fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+		let bytes: [u8; 16] = bytes
+			.try_into()
+			.map_err(|_| UuidValidationError::InvalidLength)?;
+		Self::from_bytes(bytes)
+	}
+}
+
+impl TryFrom<u128> for Uuid128 {
+	type Error = UuidValidationError;
+
+	fn try_from(value: u128) -> Result<Self, Self::Error> {
+		Self::from_u128(value)
+	}
 }
 
 impl std::str::FromStr for Uuid128 {
@@ -161,36 +625,44 @@ impl std::str::FromStr for Uuid128 {
 	/// E.g. "550e8400-e29b-41d4-a716-446655440000"
 	#[inline]
 	fn from_str(uuid_string: &str) -> Result<Self, Self::Err> {
-		// expects xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
-		if uuid_string.len() != 36 {
-			return Err(UuidParseError::InvalidLength);
-		}
+		let b = parse_hyphenated(uuid_string)?;
 
-		let s = uuid_string.as_bytes();
+		Self::from_bytes(b).map_err(|_| UuidParseError::InvalidHex)
+	}
+}
 
-		// Validate dashes at fixed positions
-		if s[8] != b'-' || s[13] != b'-' || s[18] != b'-' || s[23] != b'-' {
-			return Err(UuidParseError::InvalidHex);
-		}
+/// Parse a 8-4-4-4-12 hex string into 16 bytes, without validating version and variant.
+#[inline]
+pub(crate) fn parse_hyphenated(uuid_string: &str) -> Result<[u8; 16], UuidParseError> {
+	// expects xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx
+	if uuid_string.len() != 36 {
+		return Err(UuidParseError::InvalidLength);
+	}
 
-		let mut b = [0u8; 16];
-		let mut byte_idx = 0;
-
-		// Unrolled parsing for better performance
-		// Parse segments: 8-4-4-4-12
-		for &(start, end) in &[(0, 8), (9, 13), (14, 18), (19, 23), (24, 36)] {
-			let mut i = start;
-			while i < end {
-				let h = hexval(s[i]).ok_or(UuidParseError::InvalidHex)?;
-				let l = hexval(s[i + 1]).ok_or(UuidParseError::InvalidHex)?;
-				b[byte_idx] = (h << 4) | l;
-				byte_idx += 1;
-				i += 2;
-			}
-		}
+	let s = uuid_string.as_bytes();
 
-		Self::from_bytes(b).map_err(|_| UuidParseError::InvalidHex)
+	// Validate dashes at fixed positions
+	if s[8] != b'-' || s[13] != b'-' || s[18] != b'-' || s[23] != b'-' {
+		return Err(UuidParseError::InvalidHex);
+	}
+
+	let mut b = [0u8; 16];
+	let mut byte_idx = 0;
+
+	// Unrolled parsing for better performance
+	// Parse segments: 8-4-4-4-12
+	for &(start, end) in &[(0, 8), (9, 13), (14, 18), (19, 23), (24, 36)] {
+		let mut i = start;
+		while i < end {
+			let h = hexval(s[i]).ok_or(UuidParseError::InvalidHex)?;
+			let l = hexval(s[i + 1]).ok_or(UuidParseError::InvalidHex)?;
+			b[byte_idx] = (h << 4) | l;
+			byte_idx += 1;
+			i += 2;
+		}
 	}
+
+	Ok(b)
 }
 
 // 256 エントリ、各エントリは 2 バイト (ascii nibble -> two ascii chars)
@@ -233,64 +705,67 @@ impl std::fmt::Display for Uuid128 {
 	/// ```
 	#[inline]
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		// hex_out: 32 bytes (2 chars per input byte)
-		let mut hex_out = [0u8; 32];
-
-		// expand each input byte into 2 ascii chars using table lookup
-		let b = &self.bytes;
-		// unroll a bit manually for speed
-		hex_out[0..2].copy_from_slice(&HEX_PAIR_TABLE[b[0] as usize]);
-		hex_out[2..4].copy_from_slice(&HEX_PAIR_TABLE[b[1] as usize]);
-		hex_out[4..6].copy_from_slice(&HEX_PAIR_TABLE[b[2] as usize]);
-		hex_out[6..8].copy_from_slice(&HEX_PAIR_TABLE[b[3] as usize]);
-		hex_out[8..10].copy_from_slice(&HEX_PAIR_TABLE[b[4] as usize]);
-		hex_out[10..12].copy_from_slice(&HEX_PAIR_TABLE[b[5] as usize]);
-		hex_out[12..14].copy_from_slice(&HEX_PAIR_TABLE[b[6] as usize]);
-		hex_out[14..16].copy_from_slice(&HEX_PAIR_TABLE[b[7] as usize]);
-		hex_out[16..18].copy_from_slice(&HEX_PAIR_TABLE[b[8] as usize]);
-		hex_out[18..20].copy_from_slice(&HEX_PAIR_TABLE[b[9] as usize]);
-		hex_out[20..22].copy_from_slice(&HEX_PAIR_TABLE[b[10] as usize]);
-		hex_out[22..24].copy_from_slice(&HEX_PAIR_TABLE[b[11] as usize]);
-		hex_out[24..26].copy_from_slice(&HEX_PAIR_TABLE[b[12] as usize]);
-		hex_out[26..28].copy_from_slice(&HEX_PAIR_TABLE[b[13] as usize]);
-		hex_out[28..30].copy_from_slice(&HEX_PAIR_TABLE[b[14] as usize]);
-		hex_out[30..32].copy_from_slice(&HEX_PAIR_TABLE[b[15] as usize]);
-
-		// final 36-byte output with dashes at positions 8,13,18,23
-		let mut out = [0u8; 36];
-		// segments:
-		// out[0..8]   <- hex_out[0..8]
-		out[0..8].copy_from_slice(&hex_out[0..8]);
-		out[8] = b'-';
-
-		// out[9..13]  <- hex_out[8..12]
-		out[9..13].copy_from_slice(&hex_out[8..12]);
-		out[13] = b'-';
-
-		// out[14..18] <- hex_out[12..16]
-		out[14..18].copy_from_slice(&hex_out[12..16]);
-		out[18] = b'-';
-
-		// out[19..23] <- hex_out[16..20]
-		out[19..23].copy_from_slice(&hex_out[16..20]);
-		out[23] = b'-';
-
-		// out[24..36] <- hex_out[20..32] (12 chars)
-		out[24..36].copy_from_slice(&hex_out[20..32]);
-
-		// safe: we only put ASCII hex and '-'
-		let s = unsafe { std::str::from_utf8_unchecked(&out) };
-		f.write_str(s)
+		write_hyphenated(&self.bytes, f)
 	}
 }
 
+/// Format 16 bytes into 8-4-4-4-12 hex string, without validating version and variant.
+#[inline]
+pub(crate) fn write_hyphenated(b: &[u8; 16], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+	// hex_out: 32 bytes (2 chars per input byte)
+	let mut hex_out = [0u8; 32];
+
+	// expand each input byte into 2 ascii chars using table lookup
+	// unroll a bit manually for speed
+	hex_out[0..2].copy_from_slice(&HEX_PAIR_TABLE[b[0] as usize]);
+	hex_out[2..4].copy_from_slice(&HEX_PAIR_TABLE[b[1] as usize]);
+	hex_out[4..6].copy_from_slice(&HEX_PAIR_TABLE[b[2] as usize]);
+	hex_out[6..8].copy_from_slice(&HEX_PAIR_TABLE[b[3] as usize]);
+	hex_out[8..10].copy_from_slice(&HEX_PAIR_TABLE[b[4] as usize]);
+	hex_out[10..12].copy_from_slice(&HEX_PAIR_TABLE[b[5] as usize]);
+	hex_out[12..14].copy_from_slice(&HEX_PAIR_TABLE[b[6] as usize]);
+	hex_out[14..16].copy_from_slice(&HEX_PAIR_TABLE[b[7] as usize]);
+	hex_out[16..18].copy_from_slice(&HEX_PAIR_TABLE[b[8] as usize]);
+	hex_out[18..20].copy_from_slice(&HEX_PAIR_TABLE[b[9] as usize]);
+	hex_out[20..22].copy_from_slice(&HEX_PAIR_TABLE[b[10] as usize]);
+	hex_out[22..24].copy_from_slice(&HEX_PAIR_TABLE[b[11] as usize]);
+	hex_out[24..26].copy_from_slice(&HEX_PAIR_TABLE[b[12] as usize]);
+	hex_out[26..28].copy_from_slice(&HEX_PAIR_TABLE[b[13] as usize]);
+	hex_out[28..30].copy_from_slice(&HEX_PAIR_TABLE[b[14] as usize]);
+	hex_out[30..32].copy_from_slice(&HEX_PAIR_TABLE[b[15] as usize]);
+
+	// final 36-byte output with dashes at positions 8,13,18,23
+	let mut out = [0u8; 36];
+	// segments:
+	// out[0..8]   <- hex_out[0..8]
+	out[0..8].copy_from_slice(&hex_out[0..8]);
+	out[8] = b'-';
+
+	// out[9..13]  <- hex_out[8..12]
+	out[9..13].copy_from_slice(&hex_out[8..12]);
+	out[13] = b'-';
+
+	// out[14..18] <- hex_out[12..16]
+	out[14..18].copy_from_slice(&hex_out[12..16]);
+	out[18] = b'-';
+
+	// out[19..23] <- hex_out[16..20]
+	out[19..23].copy_from_slice(&hex_out[16..20]);
+	out[23] = b'-';
+
+	// out[24..36] <- hex_out[20..32] (12 chars)
+	out[24..36].copy_from_slice(&hex_out[20..32]);
+
+	// safe: we only put ASCII hex and '-'
+	let s = unsafe { std::str::from_utf8_unchecked(&out) };
+	f.write_str(s)
+}
+
 #[inline]
 fn build_sip_input_from_v7(u: &Uuid128, msg: &mut [u8; 10]) {
-	// [low-nibble of b6][b7][b8&0x3F][b9..b15]
-	msg[0] = u.bytes[6] & 0x0F;
-	msg[1] = u.bytes[7];
-	msg[2] = u.bytes[8] & 0x3F;
-	msg[3..10].copy_from_slice(&u.bytes[9..16]);
+	// [rand_a (big-endian u16)][rand_b (big-endian u64)]
+	msg[0..2].copy_from_slice(&u.rand_a().to_be_bytes());
+	msg[2..10].copy_from_slice(&u.rand_b().to_be_bytes());
 }
 
 #[cfg(test)]
@@ -311,20 +786,40 @@ mod tests {
 		assert_eq!((u.bytes[8] & 0xC0), 0x80);
 	}
 
-	fn craft_v7(uuid: &mut Uuid128, ts_ms_48: u64, rand_a_12: u16, rand_b_62: u64) {
-		uuid.bytes.fill(0);
-		write_48_big_endian(
-			&mut uuid.bytes[0..6].try_into().unwrap(),
-			ts_ms_48 & 0x0000FFFFFFFFFFFF,
+	#[test]
+	fn test_rand_fields() {
+		let u = UuidV7Builder::new()
+			.timestamp_ms(0x0123_4567_89AB)
+			.rand_a(0x0FFF)
+			.rand_b((1 << 62) - 1)
+			.build();
+
+		assert_eq!(u.uuid_version(), 7);
+		assert_eq!((u.bytes[8] & 0xC0), 0x80);
+		assert_eq!(u.rand_a(), 0x0FFF);
+		assert_eq!(u.rand_b(), (1 << 62) - 1);
+		assert_eq!(
+			read_48_big_endian(u.bytes[0..6].try_into().unwrap()),
+			0x0123_4567_89AB
 		);
-		uuid.set_version(7);
-		uuid.bytes[6] = (uuid.bytes[6] & 0xF0) | ((rand_a_12 >> 8) as u8 & 0x0F);
-		uuid.bytes[7] = (rand_a_12 & 0xFF) as u8;
-		uuid.set_variant_rfc4122();
-		uuid.bytes[8] = (uuid.bytes[8] & 0xC0) | ((rand_b_62 >> 56) as u8 & 0x3F);
-		for i in 0..7 {
-			uuid.bytes[9 + i] = ((rand_b_62 >> (8 * (6 - i))) & 0xFF) as u8;
-		}
+
+		let mut msg = [0u8; 10];
+		build_sip_input_from_v7(&u, &mut msg);
+		assert_eq!(
+			msg,
+			[0x0F, 0xFF, 0x3F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+		);
+	}
+
+	#[test]
+	fn test_bounds_clamp_timestamp() {
+		// truncated, 2^48 + 5 would become 5
+		let lo = Uuid128::min_for_timestamp((1 << 48) + 5);
+		let hi = Uuid128::max_for_timestamp(u64::MAX);
+		assert_eq!(lo.timestamp_ms(), MAX_TIMESTAMP_MS);
+		assert_eq!(hi.timestamp_ms(), MAX_TIMESTAMP_MS);
+		assert_eq!(lo, Uuid128::min_for_timestamp(MAX_TIMESTAMP_MS));
+		assert_eq!(hi.to_string(), "ffffffff-ffff-7fff-bfff-ffffffffffff");
 	}
 
 	#[test]
@@ -335,12 +830,15 @@ mod tests {
 		};
 
 		for i in 0..16 {
-			let mut u7 = Uuid128::empty();
-			let timestamp = ((0x100000 * i as u64) + 123) as u64;
+			let timestamp = (0x100000 * i as u64) + 123;
 			let random = ((0x0AAA ^ (i * 7) as u32) & 0x0FFF) as u16;
 			let rb = (0x0123456789ABCDEF ^ (0x1111111111111111 * i as u64)) & ((1 << 62) - 1);
 
-			craft_v7(&mut u7, timestamp, random, rb);
+			let u7 = UuidV7Builder::new()
+				.timestamp_ms(timestamp)
+				.rand_a(random)
+				.rand_b(rb)
+				.build();
 			assert_eq!(u7.uuid_version(), 7); // ensure manual creation worked
 
 			let facade = u7.encode_as_v4facade(&key);
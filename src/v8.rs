@@ -0,0 +1,248 @@
+use crate::builder::UuidV7Builder;
+use crate::error::{UuidParseError, UuidValidationError, V8FacadeError};
+use crate::key::UuidV47Key;
+use crate::siphash::siphash24;
+use crate::uuid::{parse_hyphenated, Uuid128};
+
+/// Maximum key ID carried by a `UUIDv8` facade.
+pub const V8_MAX_KEY_ID: u8 = 0x0F;
+
+const TIMESTAMP_BITS: u32 = 44;
+const TIMESTAMP_MASK: u64 = (1 << TIMESTAMP_BITS) - 1;
+
+/// SipHash message `[key ID][rand_a (big-endian u16)][rand_b (big-endian u64)]`.
+///
+/// One byte longer than the `UUIDv4` facade message, so both never share a mask.
+fn sip_input(key_id: u8, uuid: &Uuid128) -> [u8; 11] {
+	let mut msg = [0u8; 11];
+	msg[0] = key_id;
+	msg[1..3].copy_from_slice(&uuid.rand_a().to_be_bytes());
+	msg[3..11].copy_from_slice(&uuid.rand_b().to_be_bytes());
+	msg
+}
+
+impl Uuid128 {
+	/// Encode this `UUIDv7` into `UUIDv8` facade carrying `key_id`.
+	///
+	/// Layout: `[key ID (4)][masked timestamp (44)][version 8][rand_a (12)][variant][rand_b (62)]`.<br>
+	/// The key ID is readable without the key, so keys can be rotated: decode with the key of
+	/// [`Uuid128::v8_key_id`]. The random bits are unchanged as in the `UUIDv4` facade.
+	///
+	/// The Nil and Max UUID are returned unchanged.
+	///
+	/// # Errors
+	///
+	/// * [`V8FacadeError::InvalidVersion`] - if this is not a `UUIDv7`.
+	/// * [`V8FacadeError::InvalidKeyId`] - if `key_id` is greater than [`V8_MAX_KEY_ID`].
+	/// * [`V8FacadeError::TimestampOverflow`] - if the timestamp does not fit in 44 bits (after year 2527).
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{Uuid128, UuidV47Key};
+	///
+	/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	/// let v7 = Uuid128::empty();
+	///
+	/// let facade = v7.encode_as_v8facade(&key, 3).unwrap();
+	/// assert_eq!(facade.uuid_version(), 8);
+	/// assert_eq!(facade.v8_key_id(), Some(3));
+	/// assert_eq!(facade.decode_from_v8facade(&key), Ok(v7));
+	/// ```
+	pub fn encode_as_v8facade(
+		&self,
+		key: &UuidV47Key,
+		key_id: u8,
+	) -> Result<Uuid128, V8FacadeError> {
+		if key_id > V8_MAX_KEY_ID {
+			return Err(V8FacadeError::InvalidKeyId);
+		}
+		if self.is_nil() || self.is_max() {
+			return Ok(*self);
+		}
+		if self.uuid_version() != 7 {
+			return Err(V8FacadeError::InvalidVersion);
+		}
+
+		let timestamp = self.timestamp_ms();
+		if timestamp > TIMESTAMP_MASK {
+			return Err(V8FacadeError::TimestampOverflow);
+		}
+
+		let mask = siphash24(&sip_input(key_id, self), key.k0, key.k1) & TIMESTAMP_MASK;
+		let value = (u128::from(key_id) << 124)
+			| (u128::from(timestamp ^ mask) << 80)
+			| (0x8 << 76)
+			| (self.to_u128() & ((1 << 76) - 1));
+
+		// version 8 and RFC variant (kept from the UUIDv7)
+		Ok(Uuid128::from_valid_bytes(value.to_be_bytes()))
+	}
+
+	/// Create a `UUIDv8` facade from raw 16 bytes.
+	///
+	/// Validates like [`Uuid128::from_bytes`], but accepts version 8 instead of 4 and 7.
+	/// The Nil and Max UUID are accepted as well.
+	///
+	/// # Errors
+	///
+	/// * [`UuidValidationError::InvalidVersion`] - if the version is not 8.
+	/// * [`UuidValidationError::InvalidVariant`] - if the variant is not RFC 4122.
+	pub fn from_bytes_v8facade(bytes: [u8; 16]) -> Result<Self, UuidValidationError> {
+		Self::from_bytes_with_versions(bytes, &[8])
+	}
+
+	/// Parse a `UUIDv8` facade string, see [`Uuid128::from_bytes_v8facade`].
+	///
+	/// # Errors
+	///
+	/// Returns an error if the string is not a 8-4-4-4-12 hex `UUIDv8`, like [`std::str::FromStr`].
+	///
+	/// # Examples
+	/// ```
+	/// use uuid47::{Uuid128, UuidV47Key};
+	///
+	/// let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	/// let facade = Uuid128::empty().encode_as_v8facade(&key, 3).unwrap();
+	///
+	/// assert_eq!(Uuid128::parse_v8facade(&facade.to_string()), Ok(facade));
+	/// assert!(facade.to_string().parse::<Uuid128>().is_err());
+	/// ```
+	pub fn parse_v8facade(s: &str) -> Result<Self, UuidParseError> {
+		Self::from_bytes_v8facade(parse_hyphenated(s)?).map_err(|_| UuidParseError::InvalidHex)
+	}
+
+	/// Get the key ID of a `UUIDv8` facade, `None` for any other version.
+	#[must_use]
+	pub fn v8_key_id(&self) -> Option<u8> {
+		#[allow(clippy::cast_possible_truncation)]
+		(self.uuid_version() == 8).then(|| (self.to_u128() >> 124) as u8)
+	}
+
+	/// Decode this `UUIDv8` facade back into `UUIDv7`.
+	///
+	/// The key must be the one of [`Uuid128::v8_key_id`], see [`Uuid128::decode_from_v8facade_with`].<br>
+	/// The Nil and Max UUID are returned unchanged.
+	///
+	/// # Errors
+	///
+	/// * [`V8FacadeError::InvalidVersion`] - if this is not a `UUIDv8` facade.
+	pub fn decode_from_v8facade(&self, key: &UuidV47Key) -> Result<Uuid128, V8FacadeError> {
+		if self.is_nil() || self.is_max() {
+			return Ok(*self);
+		}
+		let key_id = self.v8_key_id().ok_or(V8FacadeError::InvalidVersion)?;
+
+		#[allow(clippy::cast_possible_truncation)]
+		let masked = (self.to_u128() >> 80) as u64 & TIMESTAMP_MASK;
+		let mask = siphash24(&sip_input(key_id, self), key.k0, key.k1) & TIMESTAMP_MASK;
+
+		Ok(UuidV7Builder::new()
+			.timestamp_ms(masked ^ mask)
+			.rand_a(self.rand_a())
+			.rand_b(self.rand_b())
+			.build())
+	}
+
+	/// Decode this `UUIDv8` facade with the key selected by its key ID.
+	///
+	/// # Errors
+	///
+	/// * [`V8FacadeError::InvalidVersion`] - if this is not a `UUIDv8` facade.
+	/// * [`V8FacadeError::UnknownKeyId`] - if `key_for` returns no key for the key ID.
+	pub fn decode_from_v8facade_with<'k>(
+		&self,
+		key_for: impl FnOnce(u8) -> Option<&'k UuidV47Key>,
+	) -> Result<Uuid128, V8FacadeError> {
+		if self.is_nil() || self.is_max() {
+			return Ok(*self);
+		}
+		let key_id = self.v8_key_id().ok_or(V8FacadeError::InvalidVersion)?;
+		let key = key_for(key_id).ok_or(V8FacadeError::UnknownKeyId)?;
+
+		self.decode_from_v8facade(key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_v8_facade_roundtrip() {
+		let keys = [
+			UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210),
+			UuidV47Key::new(1, 2),
+		];
+
+		for i in 0..256u64 {
+			let v7 = UuidV7Builder::new()
+				.timestamp_ms(i.wrapping_mul(0x9e37_79b9_7f4a) & TIMESTAMP_MASK)
+				.rand_a(i.wrapping_mul(31) as u16)
+				.rand_b(i.wrapping_mul(0x2545_f491_4f6c_dd1d))
+				.build();
+			let key_id = (i % 2) as u8;
+
+			let facade = v7
+				.encode_as_v8facade(&keys[key_id as usize], key_id)
+				.unwrap();
+			assert_eq!(facade.uuid_version(), 8);
+			assert_eq!(facade.v8_key_id(), Some(key_id));
+			assert!(matches!(
+				Uuid128::from_bytes(facade.into_bytes()),
+				Err(UuidValidationError::InvalidVersion)
+			));
+			assert_eq!(
+				Uuid128::from_bytes_v8facade(facade.into_bytes()).unwrap(),
+				facade
+			);
+			assert_eq!(Uuid128::parse_v8facade(&facade.to_string()), Ok(facade));
+			assert_eq!(
+				facade.decode_from_v8facade_with(|id| keys.get(id as usize)),
+				Ok(v7)
+			);
+		}
+	}
+
+	#[test]
+	fn test_v8_facade_errors() {
+		let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+		let v7 = Uuid128::empty();
+
+		assert_eq!(
+			v7.encode_as_v8facade(&key, 16),
+			Err(V8FacadeError::InvalidKeyId)
+		);
+		assert_eq!(
+			Uuid128::max_for_timestamp(1 << 44).encode_as_v8facade(&key, 0),
+			Err(V8FacadeError::TimestampOverflow)
+		);
+		let v4 = v7.encode_as_v4facade(&key);
+		assert_eq!(
+			v4.encode_as_v8facade(&key, 0),
+			Err(V8FacadeError::InvalidVersion)
+		);
+		assert_eq!(
+			v4.decode_from_v8facade(&key),
+			Err(V8FacadeError::InvalidVersion)
+		);
+		assert_eq!(v4.v8_key_id(), None);
+		assert!(matches!(
+			Uuid128::from_bytes_v8facade(v4.into_bytes()),
+			Err(UuidValidationError::InvalidVersion)
+		));
+		assert_eq!(
+			Uuid128::parse_v8facade("not-a-uuid"),
+			Err(UuidParseError::InvalidLength)
+		);
+
+		let facade = v7.encode_as_v8facade(&key, 5).unwrap();
+		assert_eq!(
+			facade.decode_from_v8facade_with(|_| None),
+			Err(V8FacadeError::UnknownKeyId)
+		);
+		assert_eq!(
+			Uuid128::nil().encode_as_v8facade(&key, 5),
+			Ok(Uuid128::nil())
+		);
+	}
+}
@@ -0,0 +1,96 @@
+use crate::error::TestVectorError;
+use crate::key::UuidV47Key;
+use crate::uuid::Uuid128;
+
+/// Raw conformance test vectors in CSV format (`k0,k1,v7,facade`).
+///
+/// Lines starting with `#` are comments. This is the content of `vectors/uuidv47.csv`
+/// in the repository, intended to be shared with other `UUIDv47` implementations.
+pub const TEST_VECTORS_CSV: &str = include_str!("../vectors/uuidv47.csv");
+
+/// A conformance test vector for the reference `UUIDv47` transform.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct TestVector {
+	/// The key used for the transform.
+	pub key: UuidV47Key,
+
+	/// The input `UUIDv7`.
+	pub v7: Uuid128,
+
+	/// The expected `UUIDv4` facade.
+	pub facade: Uuid128,
+}
+
+/// Get the conformance test vectors for the reference `UUIDv47` transform.
+///
+/// # Examples
+/// ```
+/// for vector in uuid47::test_vectors()? {
+///     assert_eq!(vector.v7.encode_as_v4facade(&vector.key), vector.facade);
+///     assert_eq!(vector.facade.decode_from_v4facade(&vector.key), vector.v7);
+/// }
+/// # Ok::<(), uuid47::TestVectorError>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the embedded vector file is malformed, which is covered by the test suite.
+pub fn test_vectors() -> Result<Vec<TestVector>, TestVectorError> {
+	parse_vectors(TEST_VECTORS_CSV)
+}
+
+fn parse_vectors(csv: &str) -> Result<Vec<TestVector>, TestVectorError> {
+	csv.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.is_empty() && !line.starts_with('#') && !line.starts_with("k0,"))
+		.map(|(index, line)| {
+			let malformed = || TestVectorError::Malformed(index + 1);
+
+			let mut fields = line.split(',');
+			let mut next = || fields.next().ok_or_else(malformed);
+
+			let k0 = u64::from_str_radix(next()?, 16).map_err(|_| malformed())?;
+			let k1 = u64::from_str_radix(next()?, 16).map_err(|_| malformed())?;
+			let v7 = next()?.parse().map_err(|_| malformed())?;
+			let facade = next()?.parse().map_err(|_| malformed())?;
+			if fields.next().is_some() {
+				return Err(malformed());
+			}
+
+			Ok(TestVector {
+				key: UuidV47Key::new(k0, k1),
+				v7,
+				facade,
+			})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_vectors() {
+		let v7 = "00000000-0000-7000-8000-000000000000";
+		let facade = "22d97126-9609-4000-8000-000000000000";
+
+		let csv = format!(
+			"# comment\nk0,k1,v7,facade\n0123456789abcdef,fedcba9876543210,{v7},{facade}\n"
+		);
+		assert_eq!(parse_vectors(&csv).unwrap().len(), 1);
+
+		for line in [
+			format!("0123456789abcdef,fedcba9876543210,{v7}"),
+			format!("0123456789abcdef,fedcba9876543210,{v7},{facade},"),
+			format!("xyz,fedcba9876543210,{v7},{facade}"),
+			format!("0123456789abcdef,fedcba9876543210,{v7},not-a-uuid"),
+		] {
+			assert_eq!(
+				parse_vectors(&format!("# comment\n{line}")),
+				Err(TestVectorError::Malformed(2)),
+				"{line}"
+			);
+		}
+	}
+}
@@ -0,0 +1,148 @@
+//! WebAssembly bindings (wasm-bindgen).
+//!
+//! Build with `--features wasm` as a `cdylib` for `wasm32-unknown-unknown`, then run `wasm-bindgen` on the output:
+//! `cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`.
+//!
+//! ```js
+//! import { Uuid128, UuidV47Key } from "uuid47";
+//!
+//! const key = UuidV47Key.fromHex(sessionKeyHex);
+//! const v7 = Uuid128.parse(facadeString).decode(key);
+//! console.log(v7.toString(), v7.inspect().timestampMs);
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::key::UuidV47Key;
+use crate::sentinel::SentinelPolicy;
+use crate::uuid::Uuid128;
+
+/// A 128-bit UUID (`UUIDv4` or `UUIDv7`).
+#[wasm_bindgen(js_name = Uuid128)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WasmUuid128(Uuid128);
+
+#[wasm_bindgen(js_class = Uuid128)]
+impl WasmUuid128 {
+	/// Parse a UUID string.
+	///
+	/// # Errors
+	///
+	/// Throws if the string is not a valid UUID.
+	pub fn parse(s: &str) -> Result<WasmUuid128, JsError> {
+		Ok(Self(s.parse()?))
+	}
+
+	/// Create a UUID from 16 bytes.
+	///
+	/// # Errors
+	///
+	/// Throws if the bytes are not a valid UUID.
+	#[wasm_bindgen(js_name = fromBytes)]
+	pub fn from_bytes(bytes: &[u8]) -> Result<WasmUuid128, JsError> {
+		Ok(Self(Uuid128::try_from(bytes)?))
+	}
+
+	/// Format as a 8-4-4-4-12 hex string.
+	#[wasm_bindgen(js_name = toString)]
+	#[allow(clippy::inherent_to_string)]
+	pub fn to_string(&self) -> String {
+		self.0.to_string()
+	}
+
+	/// Get the raw 16 bytes.
+	#[wasm_bindgen(js_name = toBytes)]
+	pub fn to_bytes(&self) -> Vec<u8> {
+		self.0.as_bytes().to_vec()
+	}
+
+	/// Encode this `UUIDv7` into `UUIDv4` facade.
+	///
+	/// # Errors
+	///
+	/// Throws if this is not a `UUIDv7`.
+	pub fn encode(&self, key: &WasmUuidV47Key) -> Result<WasmUuid128, JsError> {
+		Ok(Self(self.0.checked_encode_as_v4facade(
+			&key.0,
+			SentinelPolicy::Passthrough,
+		)?))
+	}
+
+	/// Decode this `UUIDv4` facade back into `UUIDv7`.
+	///
+	/// # Errors
+	///
+	/// Throws if this is not a `UUIDv4`.
+	pub fn decode(&self, key: &WasmUuidV47Key) -> Result<WasmUuid128, JsError> {
+		Ok(Self(self.0.checked_decode_from_v4facade(
+			&key.0,
+			SentinelPolicy::Passthrough,
+		)?))
+	}
+
+	/// Inspect the fields of this UUID.
+	pub fn inspect(&self) -> UuidInspection {
+		UuidInspection {
+			version: self.0.uuid_version(),
+			timestamp_ms: self.0.timestamp_ms() as f64,
+			is_nil: self.0.is_nil(),
+			is_max: self.0.is_max(),
+		}
+	}
+}
+
+/// Fields of a UUID, returned by `Uuid128.inspect()`.
+#[wasm_bindgen]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UuidInspection {
+	/// UUID version (4 for facades, 7 for time-ordered).
+	pub version: u8,
+
+	/// 48-bit Unix timestamp in milliseconds (meaningful for `UUIDv7` only).
+	#[wasm_bindgen(js_name = timestampMs)]
+	pub timestamp_ms: f64,
+
+	/// Whether this is the Nil UUID.
+	#[wasm_bindgen(js_name = isNil)]
+	pub is_nil: bool,
+
+	/// Whether this is the Max UUID.
+	#[wasm_bindgen(js_name = isMax)]
+	pub is_max: bool,
+}
+
+/// Key for `UUIDv47` encoding/decoding.
+#[wasm_bindgen(js_name = UuidV47Key)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WasmUuidV47Key(UuidV47Key);
+
+#[wasm_bindgen(js_class = UuidV47Key)]
+impl WasmUuidV47Key {
+	/// Creates a new key from two 64-bit halves (`BigInt`).
+	///
+	/// # Errors
+	///
+	/// Throws if the key is weak (see `UuidV47Key::validate`), unless `allowWeakKey` is true.
+	#[wasm_bindgen(constructor)]
+	pub fn new(k0: u64, k1: u64, allow_weak_key: Option<bool>) -> Result<WasmUuidV47Key, JsError> {
+		Self::checked(UuidV47Key::new(k0, k1), allow_weak_key)
+	}
+
+	/// Creates a new key from 32 hex digits (`k0` then `k1`, big-endian), see `UuidV47Key::from_str`.
+	///
+	/// # Errors
+	///
+	/// Throws if the string is not 32 hex digits,
+	/// or if the key is weak (see `UuidV47Key::validate`), unless `allowWeakKey` is true.
+	#[wasm_bindgen(js_name = fromHex)]
+	pub fn from_hex(hex: &str, allow_weak_key: Option<bool>) -> Result<WasmUuidV47Key, JsError> {
+		Self::checked(hex.parse()?, allow_weak_key)
+	}
+
+	fn checked(key: UuidV47Key, allow_weak_key: Option<bool>) -> Result<WasmUuidV47Key, JsError> {
+		if !allow_weak_key.unwrap_or(false) {
+			key.validate()?;
+		}
+		Ok(Self(key))
+	}
+}
@@ -0,0 +1,138 @@
+//! Run with `cargo test --features actix-web,keyring-json --test actix`.
+#![cfg(feature = "actix-web")]
+
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use uuid47::*;
+
+fn key() -> UuidV47Key {
+	UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210)
+}
+
+async fn get_item(id: FacadeId) -> String {
+	id.to_string()
+}
+
+async fn find_item(id: FacadeQuery) -> String {
+	id.to_string()
+}
+
+/// Keyring with an old key "a" and the primary key "b".
+#[cfg(feature = "keyring-json")]
+fn keyring() -> Keyring {
+	Keyring::from_json(
+		r#"{ "keys": [
+			{ "id": "a", "key": "5f1c9a0e7d3b2846b4e8126fa0c37d95" },
+			{ "id": "b", "key": "9d2e71c04ab8f356e1075bd93c6a28f4", "primary": true }
+		] }"#,
+	)
+	.unwrap()
+}
+
+#[cfg(feature = "keyring-json")]
+async fn get_candidates(ids: FacadeIdCandidates) -> String {
+	ids.into_inner()
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(",")
+}
+
+async fn call(uri: &str, key: Option<UuidV47Key>) -> (StatusCode, String) {
+	let mut app = App::new()
+		.route("/items/{id}", web::get().to(get_item))
+		.route("/items", web::get().to(find_item));
+	if let Some(key) = key {
+		app = app.app_data(web::Data::new(FacadeKey::new_allow_weak_key(key)));
+	}
+	let app = test::init_service(app).await;
+
+	let response = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+	let status = response.status();
+	let body = test::read_body(response).await;
+
+	(status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[test]
+fn test_actix_facade_id() {
+	actix_web::rt::System::new().block_on(async {
+		let (status, body) = call("/items/22d97126-9609-4000-8000-000000000000", Some(key())).await;
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(body, "00000000-0000-7000-8000-000000000000");
+
+		// not a UUID
+		let (status, _) = call("/items/not-a-uuid", Some(key())).await;
+		assert_eq!(status, StatusCode::BAD_REQUEST);
+
+		// UUIDv7 instead of facade
+		let (status, _) = call("/items/00000000-0000-7000-8000-000000000000", Some(key())).await;
+		assert_eq!(status, StatusCode::NOT_FOUND);
+
+		// no key in app data
+		let (status, _) = call("/items/22d97126-9609-4000-8000-000000000000", None).await;
+		assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+	});
+}
+
+#[test]
+fn test_actix_facade_query() {
+	actix_web::rt::System::new().block_on(async {
+		let (status, body) = call(
+			"/items?id=22d97126-9609-4000-8000-000000000000",
+			Some(key()),
+		)
+		.await;
+		assert_eq!(status, StatusCode::OK);
+		assert_eq!(body, "00000000-0000-7000-8000-000000000000");
+
+		// missing parameter
+		let (status, _) = call(
+			"/items?ids=22d97126-9609-4000-8000-000000000000",
+			Some(key()),
+		)
+		.await;
+		assert_eq!(status, StatusCode::BAD_REQUEST);
+
+		// no key in app data
+		let (status, _) = call("/items?id=22d97126-9609-4000-8000-000000000000", None).await;
+		assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+	});
+}
+
+#[cfg(feature = "keyring-json")]
+#[test]
+fn test_actix_facade_id_candidates() {
+	actix_web::rt::System::new().block_on(async {
+		let keyring = keyring();
+		let v7: Uuid128 = "018bcfe5-6800-7abc-9234-56789abcdef0".parse().unwrap();
+		let old = v7.encode_as_v4facade(keyring.get("a").unwrap());
+
+		let app = test::init_service(
+			App::new()
+				.app_data(web::Data::new(keyring))
+				.route("/items/{id}", web::get().to(get_candidates)),
+		)
+		.await;
+
+		let request = test::TestRequest::get()
+			.uri(&format!("/items/{old}"))
+			.to_request();
+		let response = test::call_service(&app, request).await;
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let body = test::read_body(response).await;
+		let body = String::from_utf8(body.to_vec()).unwrap();
+		assert_eq!(body.split(',').nth(1), Some(v7.to_string().as_str()));
+
+		// no keyring in app data
+		let app =
+			test::init_service(App::new().route("/items/{id}", web::get().to(get_candidates)))
+				.await;
+		let request = test::TestRequest::get()
+			.uri(&format!("/items/{old}"))
+			.to_request();
+		let response = test::call_service(&app, request).await;
+		assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+	});
+}
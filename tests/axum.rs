@@ -0,0 +1,137 @@
+//! Run with `cargo test --features axum,keyring-json --test axum`.
+#![cfg(feature = "axum")]
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use tower_service::Service;
+use uuid47::*;
+
+fn key() -> UuidV47Key {
+	UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210)
+}
+
+async fn get_item(id: FacadeId) -> String {
+	id.to_string()
+}
+
+struct ItemId;
+
+impl FacadeQueryParam for ItemId {
+	const NAME: &'static str = "item_id";
+}
+
+async fn find_item(id: FacadeQuery<ItemId>) -> String {
+	id.to_string()
+}
+
+/// Keyring with an old key "a" and the primary key "b".
+#[cfg(feature = "keyring-json")]
+fn keyring() -> Keyring {
+	Keyring::from_json(
+		r#"{ "keys": [
+			{ "id": "a", "key": "5f1c9a0e7d3b2846b4e8126fa0c37d95" },
+			{ "id": "b", "key": "9d2e71c04ab8f356e1075bd93c6a28f4", "primary": true }
+		] }"#,
+	)
+	.unwrap()
+}
+
+#[cfg(feature = "keyring-json")]
+async fn get_candidates(ids: FacadeIdCandidates) -> String {
+	ids.into_inner()
+		.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(",")
+}
+
+async fn call(uri: &str) -> (StatusCode, String) {
+	let mut app = Router::new()
+		.route("/items/{id}", get(get_item))
+		.route("/items", get(find_item))
+		.with_state(FacadeKey::new_allow_weak_key(key()));
+
+	let response = app
+		.call(Request::get(uri).body(Body::empty()).unwrap())
+		.await
+		.unwrap();
+	let status = response.status();
+	let body = axum::body::to_bytes(response.into_body(), 1024)
+		.await
+		.unwrap();
+
+	(status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_axum_facade_id() {
+	let (status, body) = call("/items/22d97126-9609-4000-8000-000000000000").await;
+	assert_eq!(status, StatusCode::OK);
+	assert_eq!(body, "00000000-0000-7000-8000-000000000000");
+
+	// not a UUID
+	let (status, _) = call("/items/not-a-uuid").await;
+	assert_eq!(status, StatusCode::BAD_REQUEST);
+
+	// UUIDv7 instead of facade
+	let (status, _) = call("/items/00000000-0000-7000-8000-000000000000").await;
+	assert_eq!(status, StatusCode::NOT_FOUND);
+
+	// Nil
+	let (status, _) = call("/items/00000000-0000-0000-0000-000000000000").await;
+	assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_axum_facade_query() {
+	let (status, body) = call("/items?page=1&item_id=22d97126-9609-4000-8000-000000000000").await;
+	assert_eq!(status, StatusCode::OK);
+	assert_eq!(body, "00000000-0000-7000-8000-000000000000");
+
+	// missing parameter, and a parameter that only starts with the name
+	let (status, body) = call("/items?item_id_x=22d97126-9609-4000-8000-000000000000").await;
+	assert_eq!(status, StatusCode::BAD_REQUEST);
+	assert_eq!(body, "Missing query parameter item_id");
+
+	let (status, _) = call("/items").await;
+	assert_eq!(status, StatusCode::BAD_REQUEST);
+
+	// UUIDv7 instead of facade
+	let (status, _) = call("/items?item_id=00000000-0000-7000-8000-000000000000").await;
+	assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[cfg(feature = "keyring-json")]
+#[tokio::test]
+async fn test_axum_facade_id_candidates() {
+	let keyring = keyring();
+	let v7: Uuid128 = "018bcfe5-6800-7abc-9234-56789abcdef0".parse().unwrap();
+	let old = v7.encode_as_v4facade(keyring.get("a").unwrap());
+	let new = v7.encode_as_v4facade(keyring.get("b").unwrap());
+
+	let mut app = Router::new()
+		.route("/items/{id}", get(get_candidates))
+		.with_state(std::sync::Arc::new(keyring));
+
+	for (facade, expected) in [(old, 1), (new, 0)] {
+		let response = app
+			.call(
+				Request::get(format!("/items/{facade}"))
+					.body(Body::empty())
+					.unwrap(),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.status(), StatusCode::OK);
+
+		let body = axum::body::to_bytes(response.into_body(), 1024)
+			.await
+			.unwrap();
+		let body = String::from_utf8(body.to_vec()).unwrap();
+		let candidates: Vec<&str> = body.split(',').collect();
+		assert_eq!(candidates.len(), 2);
+		assert_eq!(candidates[expected], v7.to_string());
+	}
+}
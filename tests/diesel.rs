@@ -0,0 +1,34 @@
+//! Run with `cargo test --features diesel-sqlite --test diesel`.
+#![cfg(feature = "diesel-sqlite")]
+
+use diesel::prelude::*;
+use diesel::sql_types::Binary;
+use uuid47::*;
+
+diesel::table! {
+	items (id) {
+		id -> Binary,
+	}
+}
+
+#[test]
+fn test_diesel_sqlite_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+	let mut conn = SqliteConnection::establish(":memory:")?;
+	diesel::sql_query("CREATE TABLE items (id BLOB PRIMARY KEY)").execute(&mut conn)?;
+
+	let v7 = Uuid128::min_for_timestamp(1_700_000_000_000);
+	diesel::insert_into(items::table)
+		.values(items::id.eq(v7))
+		.execute(&mut conn)?;
+
+	let back: Uuid128 = items::table.select(items::id).first(&mut conn)?;
+	assert_eq!(back, v7);
+
+	let bad = diesel::select(diesel::dsl::sql::<Binary>(
+		"x'00000000000010008000000000000000'",
+	))
+	.get_result::<Uuid128>(&mut conn);
+	assert!(bad.is_err());
+
+	Ok(())
+}
@@ -0,0 +1,93 @@
+//! Run with `cargo test --features async-graphql --test graphql`.
+#![cfg(feature = "async-graphql")]
+
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Request, Schema};
+use uuid47::graphql::{FacadeInput, FacadeOutput};
+use uuid47::*;
+
+struct Query;
+
+#[Object]
+impl Query {
+	/// Echo the internal `UUIDv7` of `id`, both as facade and as plain string.
+	async fn item(&self, ctx: &Context<'_>, id: FacadeInput) -> async_graphql::Result<Item> {
+		let v7 = id.decode(ctx)?;
+		Ok(Item { v7 })
+	}
+}
+
+struct Item {
+	v7: Uuid128,
+}
+
+#[Object]
+impl Item {
+	async fn id(&self) -> FacadeOutput {
+		FacadeOutput(self.v7)
+	}
+
+	async fn internal(&self) -> String {
+		self.v7.to_string()
+	}
+}
+
+fn key() -> UuidV47Key {
+	UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210)
+}
+
+async fn execute(query: &str) -> async_graphql::Response {
+	let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+	schema
+		.execute(Request::new(query).data(FacadeKey::new_allow_weak_key(key())))
+		.await
+}
+
+#[tokio::test]
+async fn test_graphql_facade_scalars() {
+	let response =
+		execute(r#"{ item(id: "22d97126-9609-4000-8000-000000000000") { id internal } }"#).await;
+	assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+	let data = response.data.into_json().unwrap();
+	assert_eq!(data["item"]["id"], "22d97126-9609-4000-8000-000000000000");
+	assert_eq!(
+		data["item"]["internal"],
+		"00000000-0000-7000-8000-000000000000"
+	);
+
+	// `ID` scalar is reused
+	let sdl = Schema::new(Query, EmptyMutation, EmptySubscription).sdl();
+	assert!(sdl.contains("item(id: ID!): Item!"));
+	assert!(sdl.contains("id: ID!"));
+}
+
+#[tokio::test]
+async fn test_graphql_invalid_input() {
+	let response = execute(r#"{ item(id: "not-a-uuid") { id } }"#).await;
+	assert_eq!(response.errors.len(), 1);
+	assert!(response.errors[0]
+		.message
+		.contains(&UuidParseError::InvalidLength.to_string()));
+
+	// UUIDv7 instead of facade
+	let response = execute(r#"{ item(id: "00000000-0000-7000-8000-000000000000") { id } }"#).await;
+	assert_eq!(response.errors.len(), 1);
+	assert!(response.errors[0]
+		.message
+		.contains(&UuidValidationError::InvalidVersion.to_string()));
+}
+
+#[tokio::test]
+async fn test_graphql_key() {
+	assert_eq!(FacadeKey::new(key()), Err(WeakKeyError::ExampleKey));
+
+	// a raw `UuidV47Key` in the request data is not used
+	let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+	let response = schema
+		.execute(
+			Request::new(r#"{ item(id: "22d97126-9609-4000-8000-000000000000") { id } }"#)
+				.data(key()),
+		)
+		.await;
+	assert_eq!(response.errors.len(), 1);
+}
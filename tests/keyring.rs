@@ -0,0 +1,105 @@
+//! Run with `cargo test --features keyring-json,keyring-toml --test keyring`.
+#![cfg(all(feature = "keyring-json", feature = "keyring-toml"))]
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use uuid47::*;
+
+const KEY_A: &str = "5f1c9a0e7d3b2846b4e8126fa0c37d95";
+const KEY_B: &str = "9d2e71c04ab8f356e1075bd93c6a28f4";
+
+/// Write a file readable by the owner only.
+fn write_secret(name: &str, contents: &str) -> PathBuf {
+	let path = std::env::temp_dir().join(format!("uuid47-keyring-{}-{name}", std::process::id()));
+	std::fs::write(&path, contents).unwrap();
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+	}
+
+	path
+}
+
+fn check(keyring: &Keyring) {
+	assert_eq!(keyring.primary().id, "2025-01");
+	assert_eq!(keyring.primary().key, KEY_B.parse().unwrap());
+	assert_eq!(keyring.get("2024-01"), Some(&KEY_A.parse().unwrap()));
+
+	// 2024-12-31 and 2025-01-01
+	let before = SystemTime::UNIX_EPOCH + Duration::from_secs(1_735_603_200);
+	let after = SystemTime::UNIX_EPOCH + Duration::from_secs(1_735_689_600);
+	assert_eq!(keyring.active_at(before).count(), 1);
+	assert_eq!(keyring.active_at(after).count(), 2);
+
+	// facades encoded with the primary key decode with the key of the same ID
+	let v7 = Uuid128::empty();
+	let facade = v7.encode_as_v4facade(&keyring.primary().key);
+	assert_eq!(
+		facade.decode_from_v4facade(keyring.get("2025-01").unwrap()),
+		v7
+	);
+}
+
+#[test]
+fn test_load_json() {
+	let json = format!(
+		r#"{{
+	"keys": [
+		{{ "id": "2024-01", "key": "{KEY_A}" }},
+		{{ "id": "2025-01", "key": "{KEY_B}", "activates_at": "2025-01-01", "primary": true }}
+	]
+}}"#
+	);
+	check(&Keyring::from_json(&json).unwrap());
+
+	let path = write_secret("keys.json", &json);
+	check(&KeyLoader::new().load_keyring(&path).unwrap());
+	std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_load_toml() {
+	let toml = format!(
+		r#"[[keys]]
+id = "2024-01"
+key = "{KEY_A}"
+
+[[keys]]
+id = "2025-01"
+key = "{KEY_B}"
+activates_at = "2025-01-01T00:00:00Z"
+primary = true
+"#
+	);
+	check(&Keyring::from_toml(&toml).unwrap());
+
+	let path = write_secret("keys.toml", &toml);
+	check(&KeyLoader::new().load_keyring(&path).unwrap());
+	std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_errors_do_not_leak_keys() {
+	// the key is given where a boolean is expected
+	let json =
+		format!(r#"{{ "keys": [ {{ "id": "a", "key": "{KEY_A}", "primary": "{KEY_B}" }} ] }}"#);
+	let err = Keyring::from_json(&json).unwrap_err();
+	assert_eq!(err, KeyringError::Syntax(1));
+	assert!(!err.to_string().contains(KEY_B));
+
+	let toml = format!("[[keys]]\nid = \"a\"\nkey = \"{KEY_A}\"\nprimary = \"{KEY_B}\"\n");
+	let err = Keyring::from_toml(&toml).unwrap_err();
+	assert_eq!(err, KeyringError::Syntax(4));
+	assert!(!err.to_string().contains(KEY_B));
+
+	let path = write_secret("keys.yaml", &json);
+	let err = KeyLoader::new().load_keyring(&path).unwrap_err();
+	assert_eq!(
+		err,
+		KeyLoadError::Keyring(KeySource::File(path.clone()), KeyringError::UnknownFormat)
+	);
+	std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,38 @@
+//! Run with `cargo test --features rusqlite --test rusqlite`.
+#![cfg(feature = "rusqlite")]
+
+use rusqlite::Connection;
+use uuid47::*;
+
+#[test]
+fn test_rusqlite_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+	let conn = Connection::open_in_memory()?;
+	conn.execute("CREATE TABLE items (id BLOB PRIMARY KEY)", ())?;
+
+	let v7 = Uuid128::min_for_timestamp(1_700_000_000_000);
+	conn.execute("INSERT INTO items (id) VALUES (?1)", (v7,))?;
+
+	let back: Uuid128 = conn.query_row("SELECT id FROM items", (), |row| row.get(0))?;
+	assert_eq!(back, v7);
+
+	Ok(())
+}
+
+#[test]
+fn test_rusqlite_rejects_invalid() -> Result<(), Box<dyn std::error::Error>> {
+	let conn = Connection::open_in_memory()?;
+
+	// version 1
+	let bad = conn.query_row("SELECT x'00000000000010008000000000000000'", (), |row| {
+		row.get::<_, Uuid128>(0)
+	});
+	assert!(bad.is_err());
+
+	// 15 bytes
+	let short = conn.query_row("SELECT x'000000000000700080000000000000'", (), |row| {
+		row.get::<_, Uuid128>(0)
+	});
+	assert!(short.is_err());
+
+	Ok(())
+}
@@ -0,0 +1,62 @@
+//! Run with `cargo test --features sqlx-sqlite,sqlx-postgres,sqlx/runtime-tokio --test sqlx`.
+//!
+//! The Postgres test is ignored by default, run it with `-- --include-ignored` and `DATABASE_URL` set,
+//! e.g. `postgres://localhost/postgres`.
+#![cfg(feature = "sqlx-sqlite")]
+
+use uuid47::*;
+
+#[tokio::test]
+async fn test_sqlx_sqlite_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+	use sqlx::{Connection, SqliteConnection};
+
+	let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+	sqlx::query("CREATE TABLE items (id BLOB PRIMARY KEY)")
+		.execute(&mut conn)
+		.await?;
+
+	let v7 = Uuid128::min_for_timestamp(1_700_000_000_000);
+	sqlx::query("INSERT INTO items (id) VALUES (?)")
+		.bind(v7)
+		.execute(&mut conn)
+		.await?;
+
+	let (back,): (Uuid128,) = sqlx::query_as("SELECT id FROM items")
+		.fetch_one(&mut conn)
+		.await?;
+	assert_eq!(back, v7);
+
+	// version 1 must be rejected
+	let bad: Result<(Uuid128,), _> = sqlx::query_as("SELECT x'00000000000010008000000000000000'")
+		.fetch_one(&mut conn)
+		.await;
+	assert!(bad.is_err());
+
+	Ok(())
+}
+
+#[cfg(feature = "sqlx-postgres")]
+#[tokio::test]
+#[ignore = "requires a Postgres server at DATABASE_URL"]
+async fn test_sqlx_postgres_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+	use sqlx::{Connection, PgConnection};
+
+	let url = std::env::var("DATABASE_URL")?;
+	let mut conn = PgConnection::connect(&url).await?;
+	let v7 = Uuid128::max_for_timestamp(1_700_000_000_000);
+
+	let (back, text): (Uuid128, String) = sqlx::query_as("SELECT $1::uuid, $1::uuid::text")
+		.bind(v7)
+		.fetch_one(&mut conn)
+		.await?;
+	assert_eq!(back, v7);
+	assert_eq!(text, v7.to_string());
+
+	let (parsed,): (Uuid128,) =
+		sqlx::query_as("SELECT '00000000-0000-7000-8000-000000000000'::uuid")
+			.fetch_one(&mut conn)
+			.await?;
+	assert_eq!(parsed, Uuid128::empty());
+
+	Ok(())
+}
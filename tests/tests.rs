@@ -43,3 +43,148 @@ fn test_bad_uuid_parse() {
 		Err(UuidParseError::InvalidLength)
 	));
 }
+
+#[test]
+fn test_time_range_bounds() {
+	let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+	let v7 = UuidV7Builder::new()
+		.timestamp_ms(1_700_000_000_123)
+		.rand_a(0x0123)
+		.rand_b(0x0123_4567_89AB_CDEF)
+		.build();
+
+	let lo = Uuid128::min_for_timestamp(1_700_000_000_123);
+	let hi = Uuid128::max_for_timestamp(1_700_000_000_123);
+	assert_eq!(lo.uuid_version(), 7);
+	assert_eq!(hi.uuid_version(), 7);
+	assert!(lo.to_string() <= v7.to_string() && v7.to_string() <= hi.to_string());
+
+	// Bounds must survive validation
+	assert!(lo.to_string().parse::<Uuid128>().is_ok());
+	assert!(hi.to_string().parse::<Uuid128>().is_ok());
+
+	let range = UuidV7Range::from_time_range(1_700_000_000_000, 1_700_000_000_999);
+	assert!(range.contains(&v7));
+	assert!(!range.contains(&Uuid128::min_for_timestamp(1_700_000_001_000)));
+	assert_eq!(
+		range,
+		UuidV7Range::from_time_range(1_700_000_000_999, 1_700_000_000_000)
+	);
+
+	let facade = v7.encode_as_v4facade(&key);
+	assert_eq!(facade.min_for_v4facade(&key), lo);
+}
+
+#[test]
+fn test_ordering_and_conversions() {
+	let a = Uuid128::min_for_timestamp(1);
+	let b = Uuid128::max_for_timestamp(1);
+	let c = Uuid128::min_for_timestamp(2);
+	assert!(a < b && b < c);
+
+	let mut sorted = vec![c, a, b];
+	sorted.sort();
+	assert_eq!(sorted, vec![a, b, c]);
+
+	assert_eq!(Uuid128::from_u128(b.to_u128()).unwrap(), b);
+	assert_eq!(Uuid128::try_from(b.as_ref()).unwrap(), b);
+	assert_eq!(Uuid128::from_bytes(b.into_bytes()).unwrap(), b);
+	assert_eq!(b.as_bytes().as_slice(), b.as_ref());
+
+	assert!(matches!(
+		Uuid128::try_from(&b.as_bytes()[..15]),
+		Err(UuidValidationError::InvalidLength)
+	));
+	assert!(matches!(
+		Uuid128::from_u128(0x00000000_0000_1000_8000_000000000000),
+		Err(UuidValidationError::InvalidVersion)
+	));
+	assert!(matches!(
+		Uuid128::from_u128(0x00000000_0000_7000_0000_000000000000),
+		Err(UuidValidationError::InvalidVariant)
+	));
+}
+
+#[test]
+fn test_nil_and_max() -> Result<(), Box<dyn std::error::Error>> {
+	let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+
+	let nil: Uuid128 = "00000000-0000-0000-0000-000000000000".parse()?;
+	let max: Uuid128 = "FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF".parse()?;
+	assert!(nil.is_nil() && !nil.is_max());
+	assert!(max.is_max() && !max.is_nil());
+	assert_eq!(nil, Uuid128::nil());
+	assert_eq!(max, Uuid128::max());
+
+	for sentinel in [nil, max] {
+		assert_eq!(sentinel.encode_as_v4facade(&key), sentinel);
+		assert_eq!(sentinel.decode_from_v4facade(&key), sentinel);
+		assert_eq!(
+			sentinel.checked_encode_as_v4facade(&key, SentinelPolicy::Passthrough)?,
+			sentinel
+		);
+		assert!(matches!(
+			sentinel.checked_decode_from_v4facade(&key, SentinelPolicy::Reject),
+			Err(UuidValidationError::Sentinel)
+		));
+	}
+
+	let v7 = Uuid128::min_for_timestamp(42);
+	let facade = v7.checked_encode_as_v4facade(&key, SentinelPolicy::Reject)?;
+	assert_eq!(facade, v7.encode_as_v4facade(&key));
+	assert_eq!(
+		facade.checked_decode_from_v4facade(&key, SentinelPolicy::Reject)?,
+		v7
+	);
+	assert!(matches!(
+		facade.checked_encode_as_v4facade(&key, SentinelPolicy::Reject),
+		Err(UuidValidationError::InvalidVersion)
+	));
+
+	Ok(())
+}
+
+#[test]
+fn test_full_width_scheme() {
+	let key = UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210);
+
+	for i in 0..16u64 {
+		let v7 = UuidV7Builder::new()
+			.timestamp_ms(1_700_000_000_000 + i)
+			.rand_a(0x0ABC)
+			.rand_b(0x0123_4567_89AB_CDEF ^ i)
+			.build();
+
+		let facade = v7.encode_as_v4facade_with(&key, FacadeScheme::FullWidthV1);
+		assert_eq!(facade.uuid_version(), 4);
+		assert!(facade.to_string().parse::<Uuid128>().is_ok());
+
+		// The random bits must not appear verbatim
+		let reference = v7.encode_as_v4facade_with(&key, FacadeScheme::Reference);
+		assert_eq!(reference, v7.encode_as_v4facade(&key));
+		assert_ne!(facade.as_bytes()[8..16], v7.as_bytes()[8..16]);
+		assert_ne!(facade, reference);
+
+		assert_eq!(
+			facade.decode_from_v4facade_with(&key, FacadeScheme::FullWidthV1),
+			v7
+		);
+		assert_ne!(
+			facade.decode_from_v4facade_with(&key, FacadeScheme::Reference),
+			v7
+		);
+	}
+}
+
+#[test]
+fn test_conformance_vectors() {
+	let vectors = test_vectors().unwrap();
+	assert_eq!(vectors.len(), 30);
+
+	for vector in vectors {
+		assert_eq!(vector.v7.uuid_version(), 7);
+		assert_eq!(vector.facade.uuid_version(), 4);
+		assert_eq!(vector.v7.encode_as_v4facade(&vector.key), vector.facade);
+		assert_eq!(vector.facade.decode_from_v4facade(&vector.key), vector.v7);
+	}
+}
@@ -0,0 +1,171 @@
+//! Run with `cargo test --features tower --test tower`.
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use tower_layer::Layer;
+use tower_service::Service;
+use uuid47::*;
+
+/// Records the request body and answers with `body`.
+#[derive(Clone)]
+struct Handler {
+	received: Arc<Mutex<Option<Bytes>>>,
+	body: &'static str,
+	content_type: &'static str,
+}
+
+impl Service<Request<FacadeJsonBody<Full<Bytes>>>> for Handler {
+	type Response = Response<Full<Bytes>>;
+	type Error = Infallible;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Infallible>> + Send>>;
+
+	fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, request: Request<FacadeJsonBody<Full<Bytes>>>) -> Self::Future {
+		let handler = self.clone();
+
+		Box::pin(async move {
+			let body = request.into_body().collect().await.unwrap().to_bytes();
+			*handler.received.lock().unwrap() = Some(body);
+
+			Ok(Response::builder()
+				.header(CONTENT_TYPE, handler.content_type)
+				.body(Full::new(Bytes::from_static(handler.body.as_bytes())))
+				.unwrap())
+		})
+	}
+}
+
+fn key() -> UuidV47Key {
+	UuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210)
+}
+
+const V7: &str = "00000000-0000-7000-8000-000000000000";
+const FACADE: &str = "22d97126-9609-4000-8000-000000000000";
+
+async fn call(
+	layer: &FacadeJsonLayer,
+	request_body: &'static str,
+	response_body: &'static str,
+	content_type: &'static str,
+) -> (Bytes, Bytes) {
+	let received = Arc::new(Mutex::new(None));
+	let mut service = layer.layer(Handler {
+		received: received.clone(),
+		body: response_body,
+		content_type,
+	});
+
+	let request = Request::post("/")
+		.header(CONTENT_TYPE, content_type)
+		.body(Full::new(Bytes::from_static(request_body.as_bytes())))
+		.unwrap();
+	let response = service.call(request).await.unwrap();
+	let body = response.into_body().collect().await.unwrap().to_bytes();
+
+	let received = received.lock().unwrap().take().unwrap();
+	(received, body)
+}
+
+#[tokio::test]
+async fn test_tower_rewrites_json() {
+	let layer = FacadeJsonLayer::new_allow_weak_key(key()).with_paths(["/id"]);
+
+	let (received, sent) = call(
+		&layer,
+		r#"{"id":"22d97126-9609-4000-8000-000000000000"}"#,
+		r#"{"id":"00000000-0000-7000-8000-000000000000","name":"x"}"#,
+		"application/json; charset=utf-8",
+	)
+	.await;
+
+	assert_eq!(received, format!(r#"{{"id":"{V7}"}}"#));
+	assert_eq!(sent, format!(r#"{{"id":"{FACADE}","name":"x"}}"#));
+
+	// without paths, only responses are rewritten
+	let (received, sent) = call(
+		&FacadeJsonLayer::new_allow_weak_key(key()),
+		r#"{"id":"22d97126-9609-4000-8000-000000000000"}"#,
+		r#"{"id":"00000000-0000-7000-8000-000000000000"}"#,
+		"application/json",
+	)
+	.await;
+
+	assert_eq!(received, format!(r#"{{"id":"{FACADE}"}}"#));
+	assert_eq!(sent, format!(r#"{{"id":"{FACADE}"}}"#));
+}
+
+#[tokio::test]
+async fn test_tower_preserves_document() {
+	let layer = FacadeJsonLayer::new_allow_weak_key(key());
+
+	let (_, sent) = call(
+		&layer,
+		"{}",
+		r#"{"z":0,"id":"00000000-0000-7000-8000-000000000000","n":18446744073709551616123,"a":1.50}"#,
+		"application/json",
+	)
+	.await;
+	assert_eq!(
+		sent,
+		format!(r#"{{"z":0,"id":"{FACADE}","n":18446744073709551616123,"a":1.50}}"#)
+	);
+}
+
+#[test]
+fn test_tower_weak_key() {
+	assert_eq!(
+		FacadeJsonLayer::new(key()).unwrap_err(),
+		WeakKeyError::ExampleKey
+	);
+	assert!(FacadeJsonLayer::new(UuidV47Key::new(0x5f1c9a0e7d3b2846, 0xb4e8126fa0c37d95)).is_ok());
+}
+
+#[tokio::test]
+async fn test_tower_body_limit() {
+	let layer = FacadeJsonLayer::new_allow_weak_key(key())
+		.with_paths(["/id"])
+		.with_body_limit(16);
+
+	// over the limit: passed through untouched
+	let (received, sent) = call(
+		&layer,
+		r#"{"id":"22d97126-9609-4000-8000-000000000000"}"#,
+		r#"{"id":"00000000-0000-7000-8000-000000000000"}"#,
+		"application/json",
+	)
+	.await;
+	assert_eq!(received, format!(r#"{{"id":"{FACADE}"}}"#));
+	assert_eq!(sent, format!(r#"{{"id":"{V7}"}}"#));
+}
+
+#[tokio::test]
+async fn test_tower_paths_and_passthrough() {
+	let layer = FacadeJsonLayer::new_allow_weak_key(key()).with_paths(["/id"]);
+
+	// only `/id` is rewritten
+	let (_, sent) = call(
+		&layer,
+		"{}",
+		r#"{"id":"00000000-0000-7000-8000-000000000000","other":"00000000-0000-7000-8000-000000000000"}"#,
+		"application/json",
+	)
+	.await;
+	assert_eq!(sent, format!(r#"{{"id":"{FACADE}","other":"{V7}"}}"#));
+
+	// not JSON
+	let (received, sent) = call(&layer, FACADE, V7, "text/plain").await;
+	assert_eq!(received, FACADE);
+	assert_eq!(sent, V7);
+}
@@ -0,0 +1,46 @@
+//! Run with `wasm-pack test --node -- --features wasm`,
+//! or `cargo test --target wasm32-unknown-unknown --features wasm` with `wasm-bindgen-test-runner` installed.
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use uuid47::wasm::{WasmUuid128, WasmUuidV47Key};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const V7: &str = "00000000-0000-7000-8000-000000000000";
+const FACADE: &str = "22d97126-9609-4000-8000-000000000000";
+
+fn key() -> WasmUuidV47Key {
+	WasmUuidV47Key::from_hex("0123456789abcdeffedcba9876543210", Some(true)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_parse_format() {
+	let uuid = WasmUuid128::parse(V7).unwrap();
+	assert_eq!(uuid.to_string(), V7);
+	assert_eq!(WasmUuid128::from_bytes(&uuid.to_bytes()).unwrap(), uuid);
+	assert!(WasmUuid128::parse("not-a-uuid").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_encode_decode() {
+	let key = key();
+	assert_eq!(
+		key,
+		WasmUuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210, Some(true)).unwrap()
+	);
+	assert!(WasmUuidV47Key::new(0x0123456789abcdef, 0xfedcba9876543210, None).is_err());
+
+	let facade = WasmUuid128::parse(V7).unwrap().encode(&key).unwrap();
+	assert_eq!(facade.to_string(), FACADE);
+	assert_eq!(facade.decode(&key).unwrap().to_string(), V7);
+	assert!(facade.encode(&key).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_inspect() {
+	let info = WasmUuid128::parse("018bcfe5-6800-7abc-9234-56789abcdef0")
+		.unwrap()
+		.inspect();
+	assert_eq!(info.version, 7);
+	assert_eq!(info.timestamp_ms, 1_700_000_000_000.0);
+	assert!(!info.is_nil && !info.is_max);
+}
@@ -0,0 +1,24 @@
+#!/bin/sh
+# Cross-check vectors/uuidv47.csv against the upstream C implementation.
+#
+#   git clone https://github.com/stateless-me/uuidv47 ../uuidv47
+#   vectors/check_upstream.sh ../uuidv47
+#
+# Builds vectors/gen_vectors.c with -DUUIDV47_UPSTREAM and compares its rows with the committed file,
+# exits non-zero on any difference.
+set -eu
+
+if [ $# -ne 1 ]; then
+	echo "usage: $0 <path to uuidv47 checkout>" >&2
+	exit 2
+fi
+
+root=$(cd "$(dirname "$0")/.." && pwd)
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+
+${CC:-cc} -std=c99 -O2 -DUUIDV47_UPSTREAM -I"$1" -o "$tmp/gen_vectors" "$root/vectors/gen_vectors.c"
+"$tmp/gen_vectors" | grep -v '^#' > "$tmp/upstream.csv"
+grep -v '^#' "$root/vectors/uuidv47.csv" | diff -u - "$tmp/upstream.csv"
+
+echo "vectors/uuidv47.csv matches upstream"
@@ -0,0 +1,112 @@
+/*
+ * Generate vectors/uuidv47.csv.
+ *
+ * With the upstream library (https://github.com/stateless-me/uuidv47):
+ *
+ *   cc -std=c99 -O2 -DUUIDV47_UPSTREAM -I<path to uuidv47> -o gen_vectors vectors/gen_vectors.c
+ *   ./gen_vectors > vectors/uuidv47.csv
+ *
+ * Without -DUUIDV47_UPSTREAM, the standalone port vectors/uuidv47_port.h is used as a fallback.
+ * The implementation is recorded in the header of the output,
+ * vectors/check_upstream.sh compares the committed rows with the upstream output.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#ifdef UUIDV47_UPSTREAM
+#include "uuidv47.h"
+#define IMPLEMENTATION "upstream uuidv47.h (https://github.com/stateless-me/uuidv47)"
+#else
+#include "uuidv47_port.h"
+#define IMPLEMENTATION "vectors/uuidv47_port.h (standalone C port, not the upstream header)"
+#endif
+
+static const uuidv47_key_t KEYS[] = {
+	{0x0123456789abcdefULL, 0xfedcba9876543210ULL},
+	{0x0000000000000000ULL, 0x0000000000000000ULL},
+	{0xffffffffffffffffULL, 0xffffffffffffffffULL},
+	{0x8000000000000000ULL, 0x8000000000000001ULL},
+	{0xf0e1d2c3b4a59687ULL, 0x8899aabbccddeeffULL},
+};
+
+static const char *V7[] = {
+	"00000000-0000-7000-8000-000000000000",
+	"00000000-0000-7123-8123-456789abcdef",
+	"ffffffff-ffff-7000-8000-000000000000",
+	"00000000-0000-7fff-bfff-ffffffffffff",
+	"ffffffff-ffff-7fff-bfff-ffffffffffff",
+	"018bcfe5-6800-7abc-9234-56789abcdef0",
+};
+
+static int hexval(char c) {
+	if (c >= '0' && c <= '9') return c - '0';
+	if (c >= 'a' && c <= 'f') return c - 'a' + 10;
+	return -1;
+}
+
+static uuid128_t parse(const char *s) {
+	uuid128_t u;
+	int n = 0;
+
+	memset(&u, 0, sizeof u);
+	for (; *s; s++) {
+		if (*s == '-') continue;
+		u.b[n / 2] = (unsigned char)((u.b[n / 2] << 4) | hexval(*s));
+		n++;
+	}
+	return u;
+}
+
+static void print_uuid(const uuid128_t *u) {
+	int i;
+
+	for (i = 0; i < 16; i++) {
+		printf("%02x", u->b[i]);
+		if (i == 3 || i == 5 || i == 7 || i == 9) putchar('-');
+	}
+}
+
+int main(void) {
+	size_t k, v;
+
+#ifndef UUIDV47_UPSTREAM
+	/* SipHash-2-4 paper, Appendix A: key 00..0f, message 00..0e */
+	{
+		uint8_t msg[15];
+
+		for (k = 0; k < sizeof msg; k++) msg[k] = (uint8_t)k;
+		if (uuidv47_siphash24(msg, sizeof msg, 0x0706050403020100ULL, 0x0f0e0d0c0b0a0908ULL) !=
+		    0xa129ca6149be45e5ULL) {
+			fprintf(stderr, "SipHash-2-4 self-test failed\n");
+			return 1;
+		}
+	}
+#endif
+
+	printf("# UUIDv47 conformance test vectors (reference scheme).\n");
+	printf("#\n");
+	printf("# Generated by vectors/gen_vectors.c with %s.\n", IMPLEMENTATION);
+	printf("# Cross-check them against the upstream header with vectors/check_upstream.sh.\n");
+	printf("#\n");
+	printf("# k0, k1 : 64-bit key halves as 16 hex digits (big-endian u64 values)\n");
+	printf("# v7     : input UUIDv7\n");
+	printf("# facade : expected UUIDv4 facade\n");
+	printf("#\n");
+	printf("# Cases per key: zero UUIDv7, zero timestamp, max 48-bit timestamp,\n");
+	printf("# all-ones random bits, max timestamp with all-ones random bits, typical value.\n");
+	printf("k0,k1,v7,facade\n");
+
+	for (k = 0; k < sizeof KEYS / sizeof KEYS[0]; k++) {
+		for (v = 0; v < sizeof V7 / sizeof V7[0]; v++) {
+			uuid128_t v7 = parse(V7[v]);
+			uuid128_t facade = uuidv47_encode_v4facade(v7, KEYS[k]);
+
+			printf("%016llx,%016llx,%s,", (unsigned long long)KEYS[k].k0,
+			       (unsigned long long)KEYS[k].k1, V7[v]);
+			print_uuid(&facade);
+			putchar('\n');
+		}
+	}
+
+	return 0;
+}
@@ -0,0 +1,119 @@
+/*
+ * Standalone C port of the reference UUIDv47 transform, with the same API as the
+ * upstream header-only library (https://github.com/stateless-me/uuidv47, uuidv47.h).
+ *
+ * Only used by gen_vectors.c when the upstream header is not available.
+ * It shares no code with the Rust crate: SipHash-2-4 follows the SipHash paper.
+ */
+#ifndef UUIDV47_PORT_H
+#define UUIDV47_PORT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct {
+	uint8_t b[16];
+} uuid128_t;
+
+typedef struct {
+	uint64_t k0, k1;
+} uuidv47_key_t;
+
+#define UUIDV47_ROTL(x, b) (uint64_t)(((x) << (b)) | ((x) >> (64 - (b))))
+
+#define UUIDV47_SIPROUND                                                                           \
+	do {                                                                                       \
+		v0 += v1;                                                                          \
+		v1 = UUIDV47_ROTL(v1, 13);                                                         \
+		v1 ^= v0;                                                                          \
+		v0 = UUIDV47_ROTL(v0, 32);                                                         \
+		v2 += v3;                                                                          \
+		v3 = UUIDV47_ROTL(v3, 16);                                                         \
+		v3 ^= v2;                                                                          \
+		v0 += v3;                                                                          \
+		v3 = UUIDV47_ROTL(v3, 21);                                                         \
+		v3 ^= v0;                                                                          \
+		v2 += v1;                                                                          \
+		v1 = UUIDV47_ROTL(v1, 17);                                                         \
+		v1 ^= v2;                                                                          \
+		v2 = UUIDV47_ROTL(v2, 32);                                                         \
+	} while (0)
+
+static inline uint64_t uuidv47_siphash24(const uint8_t *in, size_t len, uint64_t k0, uint64_t k1) {
+	uint64_t v0 = 0x736f6d6570736575ULL ^ k0;
+	uint64_t v1 = 0x646f72616e646f6dULL ^ k1;
+	uint64_t v2 = 0x6c7967656e657261ULL ^ k0;
+	uint64_t v3 = 0x7465646279746573ULL ^ k1;
+	uint64_t b = (uint64_t)len << 56;
+	size_t i, j;
+
+	for (i = 0; i + 8 <= len; i += 8) {
+		uint64_t m = 0;
+		for (j = 0; j < 8; j++) {
+			m |= (uint64_t)in[i + j] << (8 * j);
+		}
+		v3 ^= m;
+		UUIDV47_SIPROUND;
+		UUIDV47_SIPROUND;
+		v0 ^= m;
+	}
+	for (j = 0; i + j < len; j++) {
+		b |= (uint64_t)in[i + j] << (8 * j);
+	}
+
+	v3 ^= b;
+	UUIDV47_SIPROUND;
+	UUIDV47_SIPROUND;
+	v0 ^= b;
+	v2 ^= 0xff;
+	UUIDV47_SIPROUND;
+	UUIDV47_SIPROUND;
+	UUIDV47_SIPROUND;
+	UUIDV47_SIPROUND;
+
+	return v0 ^ v1 ^ v2 ^ v3;
+}
+
+/* [rand_a (12 bits, big-endian u16)][rand_b (62 bits, big-endian u64)] */
+static inline void uuidv47_sip_input(const uuid128_t *u, uint8_t msg[10]) {
+	int i;
+
+	msg[0] = u->b[6] & 0x0F;
+	msg[1] = u->b[7];
+	msg[2] = u->b[8] & 0x3F;
+	for (i = 9; i < 16; i++) {
+		msg[i - 6] = u->b[i];
+	}
+}
+
+static inline uuid128_t uuidv47_transform(uuid128_t u, uuidv47_key_t key, uint8_t version) {
+	uint8_t msg[10];
+	uint64_t mask, ts = 0;
+	int i;
+
+	uuidv47_sip_input(&u, msg);
+	mask = uuidv47_siphash24(msg, sizeof msg, key.k0, key.k1) & 0xFFFFFFFFFFFFULL;
+
+	for (i = 0; i < 6; i++) {
+		ts = (ts << 8) | u.b[i];
+	}
+	ts ^= mask;
+	for (i = 5; i >= 0; i--) {
+		u.b[i] = (uint8_t)ts;
+		ts >>= 8;
+	}
+
+	u.b[6] = (uint8_t)((u.b[6] & 0x0F) | (version << 4));
+	u.b[8] = (uint8_t)((u.b[8] & 0x3F) | 0x80);
+	return u;
+}
+
+static inline uuid128_t uuidv47_encode_v4facade(uuid128_t v7, uuidv47_key_t key) {
+	return uuidv47_transform(v7, key, 4);
+}
+
+static inline uuid128_t uuidv47_decode_v4facade(uuid128_t facade, uuidv47_key_t key) {
+	return uuidv47_transform(facade, key, 7);
+}
+
+#endif